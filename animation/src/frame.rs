@@ -0,0 +1,190 @@
+use std::convert::TryFrom;
+
+use grid::{Coordinate, Grid, Point, SparseGrid};
+
+/// Something which can be drawn as a frame of an animation, a line of text to a row
+pub trait Frame {
+    fn render(&self) -> String;
+
+    /// The frame as an image whose pixels are indices into a palette, if it can be drawn as one
+    fn indexed(&self) -> Option<Grid<u8>> {
+        None
+    }
+}
+
+/// Draw the points of a sparse grid inside its bounds as an image, a pixel to a point
+fn sparse_image<T, C: Coordinate>(
+    map: &SparseGrid<T, C>,
+    mut pixel: impl FnMut(&T) -> Option<u8>,
+    missing: u8,
+) -> Option<Grid<u8>> {
+    let (min, max) = map.bounds()?;
+    let size = |from: C, to: C| usize::try_from((to.to_i128() - from.to_i128()) as u128 + 1).ok();
+
+    let mut image = Grid::new(size(min.x, max.x)?, size(min.y, max.y)?, missing);
+    for (point, cell) in map.iter() {
+        let offset = Point::new(
+            (point.x.to_i128() - min.x.to_i128()) as usize,
+            (point.y.to_i128() - min.y.to_i128()) as usize,
+        );
+        image[offset] = pixel(cell)?;
+    }
+    Some(image)
+}
+
+fn grid_image<T>(map: &Grid<T>, mut pixel: impl FnMut(&T) -> Option<u8>) -> Option<Grid<u8>> {
+    let mut image = Grid::new(map.width(), map.height(), 0);
+    for (point, cell) in map.iter() {
+        image[point] = pixel(cell)?;
+    }
+    Some(image)
+}
+
+impl Frame for str {
+    fn render(&self) -> String {
+        self.to_owned()
+    }
+}
+
+impl Frame for String {
+    fn render(&self) -> String {
+        self.clone()
+    }
+}
+
+fn block(cell: bool) -> char {
+    if cell {
+        '█'
+    } else {
+        ' '
+    }
+}
+
+/// Grids of booleans are drawn as blocks where they're true, and as images of palette index 1
+/// where they're true and 0 elsewhere
+impl Frame for Grid<bool> {
+    fn render(&self) -> String {
+        Grid::render(self, |&cell| block(cell))
+    }
+
+    fn indexed(&self) -> Option<Grid<u8>> {
+        grid_image(self, |&cell| Some(cell as u8))
+    }
+}
+
+impl<C: Coordinate> Frame for SparseGrid<bool, C> {
+    fn render(&self) -> String {
+        SparseGrid::render(self, |cell| block(cell == Some(&true)))
+    }
+
+    fn indexed(&self) -> Option<Grid<u8>> {
+        sparse_image(self, |&cell| Some(cell as u8), 0)
+    }
+}
+
+/// A map of integer tiles, drawn with the character at each tile's place in a palette. Tiles
+/// outside the palette are drawn as `?`, and the points a sparse grid has no tile for as spaces. As
+/// images, each tile is its own palette index, and the points a sparse grid has no tile for are
+/// drawn like tile 0.
+pub struct Tiles<'a, M> {
+    map: &'a M,
+    palette: Vec<char>,
+}
+
+impl<'a, M> Tiles<'a, M> {
+    pub fn new(map: &'a M, palette: &str) -> Self {
+        Self {
+            map,
+            palette: palette.chars().collect(),
+        }
+    }
+
+    fn tile(&self, tile: i64) -> char {
+        usize::try_from(tile)
+            .ok()
+            .and_then(|tile| self.palette.get(tile))
+            .copied()
+            .unwrap_or('?')
+    }
+}
+
+impl Frame for Tiles<'_, Grid<i64>> {
+    fn render(&self) -> String {
+        self.map.render(|&tile| self.tile(tile))
+    }
+
+    fn indexed(&self) -> Option<Grid<u8>> {
+        grid_image(self.map, |&tile| u8::try_from(tile).ok())
+    }
+}
+
+impl<C: Coordinate> Frame for Tiles<'_, SparseGrid<i64, C>> {
+    fn render(&self) -> String {
+        self.map
+            .render(|tile| tile.map_or(' ', |&tile| self.tile(tile)))
+    }
+
+    fn indexed(&self) -> Option<Grid<u8>> {
+        sparse_image(self.map, |&tile| u8::try_from(tile).ok(), 0)
+    }
+}
+
+/// A frame with a line of text under it, which is left out of its image
+pub struct Captioned<F> {
+    frame: F,
+    caption: String,
+}
+
+impl<F: Frame> Captioned<F> {
+    pub fn new(frame: F, caption: String) -> Self {
+        Self { frame, caption }
+    }
+}
+
+impl<F: Frame> Frame for Captioned<F> {
+    fn render(&self) -> String {
+        format!("{}\n{}", self.frame.render(), self.caption)
+    }
+
+    fn indexed(&self) -> Option<Grid<u8>> {
+        self.frame.indexed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn booleans() {
+        let mut grid = Grid::new(3, 1, false);
+        grid[Point::<u8>::new(1, 0)] = true;
+        assert_eq!(Frame::render(&grid), " █ ");
+
+        let sparse = vec![(Point::new(0, 0), true), (Point::new(0, 1), false)]
+            .into_iter()
+            .collect::<SparseGrid<_>>();
+        assert_eq!(Frame::render(&sparse), "█\n ");
+        assert_eq!(
+            sparse.indexed().unwrap().render(|&p| (b'0' + p) as char),
+            "1\n0"
+        );
+    }
+
+    #[test]
+    fn tiles() {
+        let map = vec![(Point::new(0, 0), 1), (Point::new(2, 0), 7)]
+            .into_iter()
+            .collect::<SparseGrid<i64>>();
+        let tiles = Tiles::new(&map, ".#");
+        assert_eq!(tiles.render(), "# ?");
+        assert_eq!(
+            tiles.indexed().unwrap().render(|&p| (b'0' + p) as char),
+            "107"
+        );
+
+        let captioned = Captioned::new(tiles, "score 3".to_owned());
+        assert_eq!(captioned.render(), "# ?\nscore 3");
+        assert_eq!(captioned.indexed().unwrap().width(), 3);
+    }
+}
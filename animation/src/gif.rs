@@ -0,0 +1,547 @@
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    io::{self, Write},
+};
+
+use crate::{Frame, Screen};
+
+/// The most codes LZW compression can have, after which it has to start over
+const MAX_CODES: u16 = 4096;
+
+/// Writes codes of varying widths into bytes, starting from each byte's lowest bit as GIFs do
+struct Bits {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u8,
+}
+
+impl Bits {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.len;
+        self.len += width;
+        while self.len >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len != 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compress palette indices with the LZW variant GIFs use, whose codes start out a bit wider than
+/// `min_code_size` and grow as more codes are needed
+fn compress(min_code_size: u8, pixels: impl IntoIterator<Item = u8>) -> Vec<u8> {
+    let clear = 1 << min_code_size;
+    let end = clear + 1;
+
+    let mut bits = Bits {
+        bytes: Vec::new(),
+        buffer: 0,
+        len: 0,
+    };
+    let mut codes = HashMap::new();
+    let mut next = end + 1;
+    let mut width = min_code_size + 1;
+
+    bits.write(clear, width);
+
+    let mut prefix = None;
+    for pixel in pixels {
+        let code = match prefix {
+            Some(code) => code,
+            None => {
+                prefix = Some(pixel as u16);
+                continue;
+            }
+        };
+
+        if let Some(&longer) = codes.get(&(code, pixel)) {
+            prefix = Some(longer);
+            continue;
+        }
+
+        bits.write(code, width);
+        if next == MAX_CODES {
+            bits.write(clear, width);
+            codes.clear();
+            next = end + 1;
+            width = min_code_size + 1;
+        } else {
+            // A decoder only learns each code once it's read the one after it, so it's a code
+            // behind and widens its codes after the one written next
+            codes.insert((code, pixel), next);
+            if next == 1 << width {
+                width += 1;
+            }
+            next += 1;
+        }
+
+        prefix = Some(pixel as u16);
+    }
+
+    if let Some(code) = prefix {
+        bits.write(code, width);
+
+        // Reading the last code teaches the decoder one more, which may widen the end code
+        if next == 1 << width && width < 12 {
+            width += 1;
+        }
+    }
+    bits.write(end, width);
+
+    bits.finish()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// Writes the frames of an animated GIF, which loops forever, one after the other. Only the part
+/// of each frame which changed since the last one is written.
+pub struct Encoder<W: Write> {
+    out: W,
+    width: u16,
+    height: u16,
+    colors: usize,
+    min_code_size: u8,
+    delay: u16,
+    previous: Option<Vec<u8>>,
+}
+
+impl<W: Write> Encoder<W> {
+    /// Start a GIF whose frames are the given size, with pixels which are indices into the colors,
+    /// shown for `delay` hundredths of a second each
+    pub fn new(
+        mut out: W,
+        width: u16,
+        height: u16,
+        colors: &[[u8; 3]],
+        delay: u16,
+    ) -> io::Result<Self> {
+        if colors.is_empty() || colors.len() > 256 {
+            return Err(invalid("a GIF needs between 1 and 256 colors"));
+        }
+        if width == 0 || height == 0 {
+            return Err(invalid("a GIF can't be empty"));
+        }
+
+        // The color table's size is a power of two, with at least two colors in it
+        let bits = (colors.len().max(2) as u32)
+            .next_power_of_two()
+            .trailing_zeros() as u8;
+
+        out.write_all(b"GIF89a")?;
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        out.write_all(&[0xf0 | (bits - 1), 0, 0])?;
+        for idx in 0..1 << bits {
+            out.write_all(colors.get(idx).unwrap_or(&[0; 3]))?;
+        }
+
+        // Loop forever
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        Ok(Self {
+            out,
+            width,
+            height,
+            colors: colors.len(),
+            min_code_size: bits.max(2),
+            delay,
+            previous: None,
+        })
+    }
+
+    /// Add a frame, given as its pixels a row at a time from the top
+    pub fn frame(&mut self, pixels: &[u8]) -> io::Result<()> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        if pixels.len() != width * height {
+            return Err(invalid("the frame isn't the size of the GIF"));
+        }
+        if pixels.iter().any(|&pixel| pixel as usize >= self.colors) {
+            return Err(invalid(
+                "the frame has a pixel which isn't one of the GIF's colors",
+            ));
+        }
+
+        // Find the smallest rectangle around everything that changed, leaving the rest of the last
+        // frame in place. Every frame needs some pixels to be shown for its delay, so a frame which
+        // didn't change at all redraws its corner.
+        let (mut left, mut top, mut right, mut bottom) = (0, 0, width - 1, height - 1);
+        if let Some(previous) = &self.previous {
+            let changed = (0..width * height).filter(|&idx| pixels[idx] != previous[idx]);
+            let bounds = changed.fold(None, |bounds, idx| {
+                let (x, y) = (idx % width, idx / width);
+                Some(match bounds {
+                    None => (x, y, x, y),
+                    Some((left, top, right, bottom)) => {
+                        (x.min(left), y.min(top), x.max(right), y.max(bottom))
+                    }
+                })
+            });
+            (left, top, right, bottom) = bounds.unwrap_or((0, 0, 0, 0));
+        }
+
+        // A graphic control extension for the delay, keeping the frame when the next is drawn
+        self.out.write_all(&[0x21, 0xf9, 0x04, 0x04])?;
+        self.out.write_all(&self.delay.to_le_bytes())?;
+        self.out.write_all(&[0, 0])?;
+
+        self.out.write_all(&[0x2c])?;
+        for n in &[left, top, right - left + 1, bottom - top + 1] {
+            self.out.write_all(&(*n as u16).to_le_bytes())?;
+        }
+        self.out.write_all(&[0])?;
+
+        let changed =
+            (top..=bottom).flat_map(|y| (left..=right).map(move |x| pixels[y * width + x]));
+        self.out.write_all(&[self.min_code_size])?;
+        for block in compress(self.min_code_size, changed).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+        self.out.write_all(&[0])?;
+
+        self.previous = Some(pixels.to_vec());
+        Ok(())
+    }
+
+    /// End the GIF, returning what it was written into
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.write_all(&[0x3b])?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// An animation recorded into a GIF rather than drawn on the terminal. Frames are drawn from their
+/// palette indices, each of which is drawn as a square of pixels `scale` wide, and have to all be the
+/// same size. Like on the terminal, failing to draw a frame stops the recording, and the error is
+/// returned by `finish`.
+pub struct Gif<W: Write> {
+    out: Option<W>,
+    encoder: Option<Encoder<W>>,
+    colors: Vec<[u8; 3]>,
+    delay: u16,
+    scale: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Gif<W> {
+    /// Record into a writer at some frames per second, which GIFs can only keep to to the nearest
+    /// hundredth of a second, or without waiting between frames if that's zero
+    pub fn new(out: W, colors: &[[u8; 3]], fps: u32, scale: u16) -> Self {
+        Self {
+            out: Some(out),
+            encoder: None,
+            colors: colors.to_vec(),
+            delay: 100u32
+                .checked_div(fps)
+                .map_or(0, |delay| delay.clamp(1, u16::MAX as u32) as u16),
+            scale: scale.max(1) as usize,
+            error: None,
+        }
+    }
+
+    fn record(&mut self, frame: &dyn Frame) -> io::Result<()> {
+        let grid = frame
+            .indexed()
+            .ok_or_else(|| invalid("the frame can't be drawn as an image"))?;
+
+        let scale = self.scale;
+        let size = |n: usize| {
+            u16::try_from(n * scale).map_err(|_| invalid("the frame's too big for a GIF"))
+        };
+        let (width, height) = (size(grid.width())?, size(grid.height())?);
+
+        let encoder = match &mut self.encoder {
+            Some(encoder) => encoder,
+            None => {
+                let out = self.out.take().unwrap();
+                self.encoder
+                    .insert(Encoder::new(out, width, height, &self.colors, self.delay)?)
+            }
+        };
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for (point, &pixel) in grid.iter() {
+            if point.x == 0 && point.y != 0 {
+                // Repeat the row that was just finished to make it `scale` pixels tall
+                let row = pixels.len() - width as usize..pixels.len();
+                for _ in 1..scale {
+                    pixels.extend_from_within(row.clone());
+                }
+            }
+            pixels.extend((0..scale).map(|_| pixel));
+        }
+        let row = pixels.len() - width as usize..pixels.len();
+        for _ in 1..scale {
+            pixels.extend_from_within(row.clone());
+        }
+
+        encoder.frame(&pixels)
+    }
+
+    /// End the GIF, returning what it was written into, or why a frame couldn't be recorded
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        match self.encoder.take() {
+            Some(encoder) => encoder.finish(),
+            None => Err(invalid("a GIF needs at least one frame")),
+        }
+    }
+}
+
+impl<W: Write> Screen for Gif<W> {
+    fn draw(&mut self, frame: &dyn Frame) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = self.record(frame) {
+            self.error = Some(err);
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use grid::{Grid, Point};
+
+    use super::*;
+
+    /// Read the next LZW code from the data, starting from each byte's lowest bit
+    fn read_code(data: &[u8], position: &mut usize, width: u8) -> u16 {
+        let mut code = 0;
+        for bit in 0..width as usize {
+            let idx = *position + bit;
+            code |= (((data[idx / 8] >> (idx % 8)) & 1) as u16) << bit;
+        }
+        *position += width as usize;
+        code
+    }
+
+    fn decompress(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear = 1 << min_code_size;
+        let end = clear + 1;
+
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut width = 0;
+        let mut previous: Option<u16> = None;
+        let mut position = 0;
+        let mut pixels = Vec::new();
+
+        loop {
+            let code = read_code(data, &mut position, width.max(min_code_size + 1));
+            if code == clear {
+                table = (0..clear).map(|idx| vec![idx as u8]).collect();
+                table.push(vec![]);
+                table.push(vec![]);
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return pixels;
+            }
+
+            let entry = match previous {
+                None => table[code as usize].clone(),
+                Some(previous) => {
+                    let previous = table[previous as usize].clone();
+                    let entry = match table.get(code as usize) {
+                        Some(entry) => entry.clone(),
+                        None => {
+                            assert_eq!(code as usize, table.len(), "code from the future");
+                            let mut entry = previous.clone();
+                            entry.push(previous[0]);
+                            entry
+                        }
+                    };
+
+                    if table.len() < MAX_CODES as usize {
+                        let mut learned = previous;
+                        learned.push(entry[0]);
+                        table.push(learned);
+                        if table.len() == 1 << width && width < 12 {
+                            width += 1;
+                        }
+                    }
+
+                    entry
+                }
+            };
+
+            pixels.extend_from_slice(&entry);
+            previous = Some(code);
+        }
+    }
+
+    /// Decode a GIF written by `Encoder` into its colors and each of its frames in full
+    fn decode(gif: &[u8]) -> (Vec<[u8; 3]>, Vec<Vec<u8>>) {
+        let u16_at = |idx: usize| u16::from_le_bytes([gif[idx], gif[idx + 1]]) as usize;
+
+        assert_eq!(&gif[..6], b"GIF89a");
+        let (width, height) = (u16_at(6), u16_at(8));
+        assert_eq!(gif[10] & 0x80, 0x80, "there's no global color table");
+        let colors = 1 << ((gif[10] & 7) + 1);
+        let palette = gif[13..13 + colors * 3]
+            .chunks(3)
+            .map(|color| [color[0], color[1], color[2]])
+            .collect();
+
+        let mut canvas = vec![0; width * height];
+        let mut frames = Vec::new();
+        let mut idx = 13 + colors * 3;
+        loop {
+            match gif[idx] {
+                0x21 => {
+                    // Skip the extension's sub-blocks
+                    idx += 2;
+                    while gif[idx] != 0 {
+                        idx += gif[idx] as usize + 1;
+                    }
+                    idx += 1;
+                }
+
+                0x2c => {
+                    let (left, top) = (u16_at(idx + 1), u16_at(idx + 3));
+                    let (w, h) = (u16_at(idx + 5), u16_at(idx + 7));
+                    assert_eq!(gif[idx + 9], 0, "there's a local color table");
+                    let min_code_size = gif[idx + 10];
+                    idx += 11;
+
+                    let mut data = Vec::new();
+                    while gif[idx] != 0 {
+                        let len = gif[idx] as usize;
+                        data.extend_from_slice(&gif[idx + 1..idx + 1 + len]);
+                        idx += len + 1;
+                    }
+                    idx += 1;
+
+                    let pixels = decompress(min_code_size, &data);
+                    assert_eq!(pixels.len(), w * h);
+                    for (i, &pixel) in pixels.iter().enumerate() {
+                        canvas[(top + i / w) * width + left + i % w] = pixel;
+                    }
+                    frames.push(canvas.clone());
+                }
+
+                0x3b => {
+                    assert_eq!(idx, gif.len() - 1, "there's more after the trailer");
+                    return (palette, frames);
+                }
+
+                byte => panic!("unexpected block {:#x}", byte),
+            }
+        }
+    }
+
+    /// Pixels which don't compress well, to use up every code
+    fn noise(len: usize, colors: u32, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                ((state >> 16) % colors) as u8
+            })
+            .collect()
+    }
+
+    fn round_trip(width: u16, height: u16, colors: &[[u8; 3]], frames: &[Vec<u8>]) {
+        let mut encoder = Encoder::new(Vec::new(), width, height, colors, 5).unwrap();
+        for frame in frames {
+            encoder.frame(frame).unwrap();
+        }
+        let (palette, decoded) = decode(&encoder.finish().unwrap());
+
+        assert_eq!(&palette[..colors.len()], colors);
+        assert_eq!(decoded, frames);
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let colors = [[0, 0, 0], [255, 0, 0], [0, 255, 0], [0, 0, 255]];
+        round_trip(
+            3,
+            2,
+            &colors,
+            &[
+                vec![0, 1, 2, 3, 2, 1],
+                vec![0, 1, 2, 3, 2, 1],
+                vec![0, 3, 2, 3, 3, 1],
+                vec![1, 1, 1, 1, 1, 1],
+            ],
+        );
+    }
+
+    #[test]
+    fn every_code_width_round_trips() {
+        // Two colors have the narrowest codes and 256 the widest, and noise in that many colors
+        // uses up every code so the compression has to start over
+        for &colors in &[1, 2, 3, 16, 255] {
+            let palette = (0..=colors).map(|c| [c, c, c]).collect::<Vec<_>>();
+            let frames = (0..3)
+                .map(|seed| noise(97 * 61, colors as u32 + 1, seed))
+                .collect::<Vec<_>>();
+            round_trip(97, 61, &palette, &frames);
+        }
+
+        // And a frame which compresses perfectly has codes for ever longer runs
+        round_trip(300, 200, &[[0; 3]], &[vec![0; 300 * 200]]);
+    }
+
+    #[test]
+    fn encoder_errors() {
+        assert!(Encoder::new(Vec::new(), 1, 1, &[], 0).is_err());
+        assert!(Encoder::new(Vec::new(), 0, 1, &[[0; 3]], 0).is_err());
+
+        let mut encoder = Encoder::new(Vec::new(), 2, 1, &[[0; 3], [1; 3]], 0).unwrap();
+        assert!(encoder.frame(&[0]).is_err());
+        assert!(encoder.frame(&[0, 2]).is_err());
+    }
+
+    #[test]
+    fn recording_scales_frames() {
+        let colors = [[0; 3], [255; 3]];
+        let mut gif = Gif::new(Vec::new(), &colors, 25, 2);
+
+        let mut grid = Grid::new(2, 2, false);
+        gif.draw(&grid);
+        grid[Point::<u8>::new(1, 0)] = true;
+        gif.draw(&grid);
+
+        let (_, frames) = decode(&gif.finish().unwrap());
+        assert_eq!(
+            frames,
+            [
+                vec![0; 16],
+                vec![0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn recording_needs_images() {
+        let mut gif = Gif::new(Vec::new(), &[[0; 3]], 25, 1);
+        gif.draw(&String::from("text"));
+        assert!(gif.is_stopped());
+        assert!(gif.finish().is_err());
+
+        assert!(Gif::new(Vec::new(), &[[0; 3]], 25, 1).finish().is_err());
+    }
+}
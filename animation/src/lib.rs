@@ -0,0 +1,311 @@
+//! Watching the simulations in the puzzles play out on the terminal. Each frame is drawn over the
+//! last one using ANSI escape codes, at most as often as the frame rate allows, and the animation
+//! can be paused and stepped through a frame at a time from the keyboard. Frames which are images
+//! can be recorded into a GIF instead.
+
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+mod frame;
+mod gif;
+
+pub use frame::{Captioned, Frame, Tiles};
+pub use gif::{Encoder, Gif};
+
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const HOME: &str = "\x1b[H";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+
+/// Somewhere an animation's frames can be drawn, so simulations can be watched on the terminal or
+/// recorded alike
+pub trait Screen {
+    fn draw(&mut self, frame: &dyn Frame);
+
+    /// Whether frames are no longer being drawn. Simulations can use this to stop doing work only
+    /// needed to draw.
+    fn is_stopped(&self) -> bool;
+}
+
+/// What can be typed to control an animation, a line at a time, since the terminal only sends what's
+/// typed once Enter is pressed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Control {
+    /// An empty line, which pauses a playing animation and steps a paused one a frame on
+    Step,
+    Continue,
+    Stop,
+}
+
+impl Control {
+    fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "" => Some(Control::Step),
+            "c" => Some(Control::Continue),
+            "q" => Some(Control::Stop),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Playing,
+    Paused,
+
+    /// Stopped by the viewer or by failing to draw, after which frames are no longer drawn
+    Stopped,
+}
+
+/// Frames being drawn one after the other. Failing to draw a frame stops the animation rather than
+/// being reported straight away, so simulations can draw as they go without having to handle
+/// errors, and the error is returned by `finish` instead.
+pub struct Animation<W: Write = io::Stdout> {
+    out: W,
+    frame_time: Duration,
+    next_frame: Option<Instant>,
+    controls: Option<Receiver<Control>>,
+    state: State,
+    frames: usize,
+    error: Option<io::Error>,
+}
+
+impl Animation {
+    /// Animate on the terminal at some frames per second, or as fast as frames come if that's zero.
+    /// When stdin's the terminal too, what's typed into it controls the animation.
+    pub fn terminal(fps: u32) -> Self {
+        let mut animation = Self::new(io::stdout(), fps);
+
+        if io::stdin().is_terminal() {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                for line in io::stdin().lock().lines() {
+                    let control = match line {
+                        Ok(line) => Control::parse(&line),
+                        Err(_) => break,
+                    };
+
+                    if let Some(control) = control {
+                        if sender.send(control).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+            animation.controls = Some(receiver);
+        }
+
+        animation
+    }
+}
+
+impl<W: Write> Animation<W> {
+    /// Animate into a writer at some frames per second, or as fast as frames come if that's zero,
+    /// without any controls
+    pub fn new(out: W, fps: u32) -> Self {
+        Self {
+            out,
+            frame_time: if fps == 0 {
+                Duration::default()
+            } else {
+                Duration::from_secs(1) / fps
+            },
+            next_frame: None,
+            controls: None,
+            state: State::Playing,
+            frames: 0,
+            error: None,
+        }
+    }
+
+    /// How many frames have been drawn
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Draw the next frame over the last, once it's time to and unless the animation's paused or
+    /// stopped
+    pub fn draw(&mut self, frame: &(impl Frame + ?Sized)) {
+        if let Some(next_frame) = self.next_frame {
+            let now = Instant::now();
+            if next_frame > now {
+                thread::sleep(next_frame - now);
+            }
+        }
+
+        self.follow_controls();
+        if self.state == State::Stopped {
+            return;
+        }
+
+        if let Err(err) = self.write(&frame.render()) {
+            self.error = Some(err);
+            self.state = State::Stopped;
+            return;
+        }
+
+        self.frames += 1;
+        self.next_frame = Some(Instant::now() + self.frame_time);
+    }
+
+    /// Do what's been typed since the last frame, waiting for what to do if the animation's paused
+    fn follow_controls(&mut self) {
+        let controls = match &self.controls {
+            Some(controls) => controls,
+            None => return,
+        };
+
+        loop {
+            let control = if self.state == State::Paused {
+                // The viewer can't unpause an animation once stdin's closed, so it carries on
+                controls.recv().unwrap_or(Control::Continue)
+            } else {
+                match controls.try_recv() {
+                    Ok(control) => control,
+                    Err(TryRecvError::Empty) => return,
+                    Err(TryRecvError::Disconnected) => {
+                        self.controls = None;
+                        return;
+                    }
+                }
+            };
+
+            match (self.state, control) {
+                (_, Control::Stop) => {
+                    self.state = State::Stopped;
+                    return;
+                }
+                (State::Paused, Control::Step) => return,
+                (_, Control::Step) => self.state = State::Paused,
+                (_, Control::Continue) => self.state = State::Playing,
+            }
+        }
+    }
+
+    fn write(&mut self, frame: &str) -> io::Result<()> {
+        if self.frames == 0 {
+            write!(self.out, "{}{}", HIDE_CURSOR, CLEAR_SCREEN)?;
+        }
+
+        // Clearing what's left of each line as it's written over, rather than the whole screen
+        // first, keeps the screen from flickering
+        write!(self.out, "{}", HOME)?;
+        for line in frame.lines() {
+            writeln!(self.out, "{}{}", line, CLEAR_LINE)?;
+        }
+
+        write!(self.out, "frame {}", self.frames + 1)?;
+        match (&self.controls, self.state) {
+            (None, _) => {}
+            (Some(_), State::Paused) => {
+                write!(self.out, ", paused: Enter steps, c continues, q stops")?
+            }
+            (Some(_), _) => write!(self.out, ", Enter pauses, q stops")?,
+        }
+        write!(self.out, "{}\n{}", CLEAR_LINE, CLEAR_BELOW)?;
+
+        self.out.flush()
+    }
+
+    /// Give the terminal its cursor back, returning why the animation stopped drawing if a frame
+    /// couldn't be drawn
+    pub fn finish(mut self) -> io::Result<()> {
+        self.restore();
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    fn restore(&mut self) {
+        if self.frames != 0 {
+            let _ = write!(self.out, "{}", SHOW_CURSOR).and_then(|()| self.out.flush());
+            self.frames = 0;
+        }
+    }
+}
+
+/// The animation stops once the viewer stops it or a frame can't be drawn
+impl<W: Write> Screen for Animation<W> {
+    fn draw(&mut self, frame: &dyn Frame) {
+        Animation::draw(self, frame)
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.state == State::Stopped
+    }
+}
+
+/// An animation dropped without being finished still gives the terminal its cursor back
+impl<W: Write> Drop for Animation<W> {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_drawn_over_each_other() {
+        let mut out = Vec::new();
+        let mut animation = Animation::new(&mut out, 0);
+        animation.draw("ab\nc");
+        animation.draw("d");
+        assert_eq!(animation.frames(), 2);
+        animation.finish().unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[?25l\x1b[2J\
+             \x1b[Hab\x1b[K\nc\x1b[K\nframe 1\x1b[K\n\x1b[J\
+             \x1b[Hd\x1b[K\nframe 2\x1b[K\n\x1b[J\
+             \x1b[?25h"
+        );
+    }
+
+    #[test]
+    fn nothing_is_drawn_without_frames() {
+        let mut out = Vec::new();
+        Animation::new(&mut out, 30).finish().unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn controls() {
+        let (sender, receiver) = mpsc::channel();
+        let mut out = Vec::new();
+        let mut animation = Animation::new(&mut out, 0);
+        animation.controls = Some(receiver);
+
+        // Pausing waits for a step before drawing the next frame, which is drawn still paused
+        sender.send(Control::Step).unwrap();
+        sender.send(Control::Step).unwrap();
+        animation.draw("a");
+        assert_eq!(animation.state, State::Paused);
+
+        sender.send(Control::Continue).unwrap();
+        animation.draw("b");
+        assert_eq!(animation.state, State::Playing);
+
+        sender.send(Control::Stop).unwrap();
+        animation.draw("c");
+        assert!(animation.is_stopped());
+        assert_eq!(animation.frames(), 2);
+    }
+
+    #[test]
+    fn parse_controls() {
+        assert_eq!(Control::parse("\n"), Some(Control::Step));
+        assert_eq!(Control::parse("c"), Some(Control::Continue));
+        assert_eq!(Control::parse(" q "), Some(Control::Stop));
+        assert_eq!(Control::parse("x"), None);
+    }
+}
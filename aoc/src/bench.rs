@@ -0,0 +1,206 @@
+//! Benchmarks of each day's parsing and parts and of the intcode interpreter's dispatch, run by the
+//! `aoc-bench` binary
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use common::Solution;
+use intcode::{BlockCache, Interpreter};
+
+/// Every benchmark runs at least this many times, however long that takes, so its median means
+/// something
+const MIN_RUNS: usize = 5;
+
+/// The most times a benchmark runs, however fast it is
+const MAX_RUNS: usize = 100_000;
+
+/// How many times the interpreter benchmarks' programs go round their loops
+const LOOPS: i64 = 100_000;
+
+/// How long a benchmark's runs took
+pub struct Measurement {
+    pub name: String,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+}
+
+/// A benchmark, which runs its routine over and over for a time budget. Running it fails if the
+/// day's input can't be read or solved.
+pub struct Benchmark {
+    pub name: String,
+    run: Box<dyn Fn(Duration) -> common::Result<Measurement>>,
+}
+
+impl Benchmark {
+    fn new(
+        name: String,
+        run: impl Fn(&str, Duration) -> common::Result<Measurement> + 'static,
+    ) -> Self {
+        let run_name = name.clone();
+        Self {
+            name,
+            run: Box::new(move |budget| run(&run_name, budget)),
+        }
+    }
+
+    pub fn run(&self, budget: Duration) -> common::Result<Measurement> {
+        (self.run)(budget)
+    }
+}
+
+/// Time the routine until the budget's spent, giving it a fresh value from `setup` each time.
+/// Only the routine is timed.
+fn measure<I, T>(
+    name: &str,
+    budget: Duration,
+    mut setup: impl FnMut() -> I,
+    mut routine: impl FnMut(I) -> T,
+) -> Measurement {
+    let mut times = Vec::new();
+
+    let start = Instant::now();
+    while times.len() < MIN_RUNS || (start.elapsed() < budget && times.len() < MAX_RUNS) {
+        let input = setup();
+        let before = Instant::now();
+        black_box(routine(black_box(input)));
+        times.push(before.elapsed());
+    }
+
+    times.sort();
+    Measurement {
+        name: name.to_owned(),
+        runs: times.len(),
+        min: times[0],
+        median: times[times.len() / 2],
+    }
+}
+
+/// Benchmarks of the day's parsing and each of its parts. Each is checked to work once before it's
+/// timed, which also warms it up.
+fn day<S: Solution + 'static>() -> Vec<Benchmark> {
+    let mut benchmarks = vec![Benchmark::new(
+        format!("day{:02}/parse", S::DAY),
+        |name, budget| {
+            let input = common::stored_input(S::DAY)?;
+            S::parse(&input)?;
+            Ok(measure(name, budget, || (), |()| S::parse(&input)))
+        },
+    )];
+
+    for part in 1..=S::PARTS {
+        benchmarks.push(Benchmark::new(
+            format!("day{:02}/part{}", S::DAY, part),
+            move |name, budget| {
+                let solution = S::parse(&common::stored_input(S::DAY)?)?;
+                solution.part(part)?;
+                Ok(measure(name, budget, || (), |()| solution.part(part)))
+            },
+        ));
+    }
+
+    benchmarks
+}
+
+/// A program counting to `LOOPS` with absolute addressing, which is mostly arithmetic and jumps
+fn count_absolute() -> Interpreter {
+    let mut interpreter = Interpreter::new(vec![
+        1101, 0, 0, 100, // [100] = 0
+        1001, 100, 1, 100, // [100] += 1
+        1007, 100, LOOPS, 101, // [101] = [100] < LOOPS
+        1005, 101, 4,  // if [101] goto 4
+        99, //
+    ]);
+    interpreter.memory.resize(102, 0);
+    interpreter
+}
+
+/// The same program as `count_absolute`, addressing its counter relative to the base
+fn count_relative() -> Interpreter {
+    let mut interpreter = Interpreter::new(vec![
+        109, 100, // base = 100
+        21101, 0, 0, 0, // [base] = 0
+        22101, 1, 0, 0, // [base] += 1
+        21207, 0, LOOPS, 1, // [base + 1] = [base] < LOOPS
+        1205, 1, 6,  // if [base + 1] goto 6
+        99, //
+    ]);
+    interpreter.memory.resize(102, 0);
+    interpreter
+}
+
+/// A program echoing `LOOPS` inputs back as outputs, with its input queued
+fn echo() -> Interpreter {
+    let mut interpreter = Interpreter::new(vec![
+        3, 100, // [100] = input
+        4, 100, // output [100]
+        1105, 1, 0, // goto 0
+    ]);
+    interpreter.memory.resize(101, 0);
+    interpreter.input.extend(0..LOOPS);
+    interpreter
+}
+
+/// Benchmarks of running a program through the jump table with `run`, one instruction at a time
+/// with `step` and through a fresh block cache, which includes compiling its blocks
+fn interpreter(name: &'static str, program: fn() -> Interpreter) -> Vec<Benchmark> {
+    vec![
+        Benchmark::new(format!("intcode/run/{}", name), move |name, budget| {
+            Ok(measure(name, budget, program, |mut interpreter| {
+                interpreter.run();
+                interpreter
+            }))
+        }),
+        Benchmark::new(format!("intcode/step/{}", name), move |name, budget| {
+            Ok(measure(name, budget, program, |mut interpreter| {
+                while interpreter.step() {}
+                interpreter
+            }))
+        }),
+        Benchmark::new(format!("intcode/cache/{}", name), move |name, budget| {
+            Ok(measure(name, budget, program, |mut interpreter| {
+                BlockCache::new().run(&mut interpreter);
+                interpreter
+            }))
+        }),
+    ]
+}
+
+/// Every benchmark, by name
+pub fn benchmarks() -> Vec<Benchmark> {
+    let days: [fn() -> Vec<Benchmark>; 25] = [
+        day::<day01::Day01>,
+        day::<day02::Day02>,
+        day::<day03::Day03>,
+        day::<day04::Day04>,
+        day::<day05::Day05>,
+        day::<day06::Day06>,
+        day::<day07::Day07>,
+        day::<day08::Day08>,
+        day::<day09::Day09>,
+        day::<day10::Day10>,
+        day::<day11::Day11>,
+        day::<day12::Day12>,
+        day::<day13::Day13>,
+        day::<day14::Day14>,
+        day::<day15::Day15>,
+        day::<day16::Day16>,
+        day::<day17::Day17>,
+        day::<day18::Day18>,
+        day::<day19::Day19>,
+        day::<day20::Day20>,
+        day::<day21::Day21>,
+        day::<day22::Day22>,
+        day::<day23::Day23>,
+        day::<day24::Day24>,
+        day::<day25::Day25>,
+    ];
+
+    let mut benchmarks = days.iter().flat_map(|day| day()).collect::<Vec<_>>();
+    benchmarks.extend(interpreter("count_absolute", count_absolute));
+    benchmarks.extend(interpreter("count_relative", count_relative));
+    benchmarks.extend(interpreter("echo", echo));
+    benchmarks
+}
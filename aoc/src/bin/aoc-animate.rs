@@ -0,0 +1,220 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Read},
+    path::{Path, PathBuf},
+    process, thread,
+};
+
+use animation::{Animation, Gif};
+use aoc::{animate, colors};
+use intcode::STACK_SIZE;
+
+const USAGE: &str = "usage: aoc-animate [options] <day> [<input>]
+
+Plays out the puzzle of a day which happens on a map on the terminal, and prints its answer once
+it's done. The days which can be watched are 11, 13, 15, 17 and 24. The input is read from the
+given file, from stdin if that's -, and otherwise from the inputs directory, or from $AOC_INPUTS
+if it's set.
+
+While it plays, pressing Enter pauses it, and pressing Enter again steps it on a frame at a time.
+Typing c and Enter continues it, and typing q and Enter stops drawing and skips to the answer.
+
+Days 13, 15 and 24 can be recorded into an animated GIF instead, with --gif.
+
+options:
+    --fps <n>      how many frames to draw a second, 30 by default, or 0 to draw them as fast as
+                   they come
+    --gif <file>   record the animation into a GIF rather than drawing it on the terminal
+    --scale <n>    how many pixels wide each tile of a GIF is, 4 by default";
+
+struct Options {
+    fps: u32,
+    gif: Option<PathBuf>,
+    scale: u16,
+    day: u32,
+    input: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut fps = 30;
+    let mut gif = None;
+    let mut scale = 4;
+    let mut day = None;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |what| args.next().ok_or(format!("{} needs {}", arg, what));
+
+        match arg.as_str() {
+            "--fps" => {
+                let value = value("a number of frames")?;
+                fps = value
+                    .parse()
+                    .map_err(|_| format!("{:?} isn't a number of frames", value))?;
+            }
+
+            "--gif" => gif = Some(value("a file")?.into()),
+
+            "--scale" => {
+                let value = value("a number of pixels")?;
+                scale = value
+                    .parse()
+                    .ok()
+                    .filter(|&scale| scale != 0)
+                    .ok_or(format!("{:?} isn't a number of pixels", value))?;
+            }
+
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+
+            _ if day.is_none() => {
+                let parsed = arg
+                    .parse()
+                    .map_err(|_| format!("{:?} isn't a day of Advent", arg))?;
+                day = Some(parsed);
+            }
+
+            _ if input.is_none() => input = Some(arg),
+
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+
+    Ok(Options {
+        fps,
+        gif,
+        scale,
+        day: day.ok_or("which day should be animated?")?,
+        input,
+    })
+}
+
+fn read_input(day: u32, path: Option<&str>) -> common::Result<String> {
+    match path {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| format!("could not read stdin: {}", err))?;
+            Ok(input)
+        }
+
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| format!("could not read {}: {}", path, err).into()),
+
+        None => common::stored_input(day),
+    }
+}
+
+fn record(options: &Options, input: &str, path: &Path) -> common::Result<String> {
+    let colors = colors(options.day)
+        .ok_or_else(|| format!("day {} can't be recorded into a GIF", options.day))?;
+    let file = File::create(path)
+        .map_err(|err| format!("could not create {}: {}", path.display(), err))?;
+
+    let mut gif = Gif::new(BufWriter::new(file), colors, options.fps, options.scale);
+    let answer = animate(options.day, input, &mut gif)?;
+    gif.finish()
+        .map_err(|err| format!("could not record {}: {}", path.display(), err))?;
+
+    Ok(answer)
+}
+
+fn run(options: Options) -> common::Result<String> {
+    let input = read_input(options.day, options.input.as_deref())?;
+
+    if let Some(path) = &options.gif {
+        return record(&options, &input, path);
+    }
+
+    let mut animation = Animation::terminal(options.fps);
+    let answer = animate(options.day, &input, &mut animation);
+    animation
+        .finish()
+        .map_err(|err| format!("could not draw the animation: {}", err))?;
+
+    answer
+}
+
+fn main() {
+    let options = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    let answer = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || run(options))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    match answer {
+        Ok(answer) => println!("{}", answer),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn options() {
+        let options = parse(&["--fps", "0", "--gif", "out.gif", "--scale", "2", "13", "-"])
+            .ok()
+            .unwrap();
+        assert_eq!((options.fps, options.scale, options.day), (0, 2, 13));
+        assert_eq!(options.gif, Some(PathBuf::from("out.gif")));
+        assert_eq!(options.input.as_deref(), Some("-"));
+
+        let options = parse(&["24"]).ok().unwrap();
+        assert_eq!((options.fps, options.scale, options.day), (30, 4, 24));
+        assert_eq!((options.gif, options.input), (None, None));
+    }
+
+    #[test]
+    fn scale() {
+        for &(scale, err) in &[
+            ("0", "\"0\" isn't a number of pixels"),
+            ("-1", "\"-1\" isn't a number of pixels"),
+            ("big", "\"big\" isn't a number of pixels"),
+        ] {
+            assert_eq!(parse(&["--scale", scale, "13"]).err().as_deref(), Some(err));
+        }
+
+        assert_eq!(
+            parse(&["13", "--scale"]).err().as_deref(),
+            Some("--scale needs a number of pixels")
+        );
+    }
+
+    #[test]
+    fn missing_day() {
+        for args in &[&[][..], &["--fps", "10"][..]] {
+            assert_eq!(
+                parse(args).err().as_deref(),
+                Some("which day should be animated?")
+            );
+        }
+    }
+
+    #[test]
+    fn extra_arguments() {
+        assert_eq!(
+            parse(&["13", "input.txt", "more"]).err().as_deref(),
+            Some("unexpected argument \"more\"")
+        );
+        assert_eq!(
+            parse(&["13", "--verbose"]).err().as_deref(),
+            Some("unknown option --verbose")
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    thread,
+    time::Duration,
+};
+
+use aoc::bench::{self, Measurement};
+use intcode::STACK_SIZE;
+
+const USAGE: &str = "usage: aoc-bench [options] [<filter>]
+       aoc-bench [options] --compare <old> <new> [<filter>]
+
+Times each day's parsing and parts and the intcode interpreter's dispatch, running each benchmark
+over and over and printing how many runs it got and their fastest and median times. Only the
+benchmarks whose names contain the filter, like \"day16\" or \"intcode/run\", are run.
+
+With --compare, the benchmarks are run for two git revisions and their medians compared. Each
+revision is built in its own worktree under target/bench, and both read the current tree's inputs.
+Either side can instead be a file written by --save.
+
+options:
+    --time <ms>              how long to spend running each benchmark, 1000 by default
+    --save <file>            also write the times to the file, to compare against later
+    --compare <old> <new>    compare two revisions or saved files
+    --threshold <percent>    how much slower a benchmark has to get to count as a regression, 10
+                             by default
+
+exit codes:
+    0  every benchmark ran, and none regressed
+    1  a benchmark failed or regressed, or a revision couldn't be built
+    2  the arguments are wrong";
+
+struct Options {
+    time: Duration,
+    save: Option<PathBuf>,
+    compare: Option<(String, String)>,
+    threshold: f64,
+    filter: String,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        time: Duration::from_secs(1),
+        save: None,
+        compare: None,
+        threshold: 10.0,
+        filter: String::new(),
+    };
+    let mut filter = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |what| args.next().ok_or(format!("{} needs {}", arg, what));
+
+        match arg.as_str() {
+            "--time" => {
+                let time = value("a number of milliseconds")?;
+                let time = time
+                    .parse()
+                    .map_err(|_| format!("{:?} isn't a number of milliseconds", time))?;
+                options.time = Duration::from_millis(time);
+            }
+
+            "--save" => options.save = Some(value("a file")?.into()),
+
+            "--compare" => {
+                let old = value("two revisions")?;
+                options.compare = Some((old, value("two revisions")?));
+            }
+
+            "--threshold" => {
+                let threshold = value("a percentage")?;
+                options.threshold = threshold
+                    .parse()
+                    .ok()
+                    .filter(|threshold: &f64| *threshold >= 0.0)
+                    .ok_or(format!("{:?} isn't a percentage", threshold))?;
+            }
+
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+
+            _ if filter.is_none() => filter = Some(arg),
+
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+
+    if options.save.is_some() && options.compare.is_some() {
+        return Err("--save can't be used with --compare".into());
+    }
+
+    options.filter = filter.unwrap_or_default();
+    Ok(options)
+}
+
+fn print_row(name: &str, runs: &str, min: &str, median: &str, note: &str) {
+    let row = format!(
+        "{:<28}  {:>6}  {:>10}  {:>10}  {}",
+        name, runs, min, median, note
+    );
+    println!("{}", row.trim_end());
+}
+
+/// Run the benchmarks whose names contain the filter, printing each one's times as it finishes,
+/// and return the times of those which didn't fail along with whether any did
+fn run(filter: &str, time: Duration) -> (Vec<Measurement>, bool) {
+    let mut measurements = Vec::new();
+    let mut failed = false;
+
+    print_row("benchmark", "runs", "min", "median", "");
+    for benchmark in bench::benchmarks() {
+        if !benchmark.name.contains(filter) {
+            continue;
+        }
+
+        match benchmark.run(time) {
+            Ok(measurement) => {
+                print_row(
+                    &measurement.name,
+                    &measurement.runs.to_string(),
+                    &format!("{:.2?}", measurement.min),
+                    &format!("{:.2?}", measurement.median),
+                    "",
+                );
+                measurements.push(measurement);
+            }
+
+            Err(err) => {
+                print_row(&benchmark.name, "", "", "", &format!("error: {}", err));
+                failed = true;
+            }
+        }
+    }
+
+    (measurements, failed)
+}
+
+/// Write the times to the file, a benchmark to a line, as its name, runs and the fastest and
+/// median times in nanoseconds separated by tabs
+fn save(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    let contents = measurements
+        .iter()
+        .map(|measurement| {
+            format!(
+                "{}\t{}\t{}\t{}\n",
+                measurement.name,
+                measurement.runs,
+                measurement.min.as_nanos(),
+                measurement.median.as_nanos()
+            )
+        })
+        .collect::<String>();
+
+    fs::write(path, contents).map_err(|err| format!("could not write {}: {}", path.display(), err))
+}
+
+/// Parse a line written by `save`
+fn parse_measurement(line: &str) -> Option<Measurement> {
+    let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+    match line.split('\t').collect::<Vec<_>>()[..] {
+        [name, runs, min, median] => Some(Measurement {
+            name: name.to_owned(),
+            runs: runs.parse().ok()?,
+            min: nanos(min)?,
+            median: nanos(median)?,
+        }),
+        _ => None,
+    }
+}
+
+/// Read the times written to the file by `save`
+fn load(path: &Path) -> Result<Vec<Measurement>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_measurement(line).ok_or(format!(
+                "line {} of {} isn't a benchmark's times",
+                idx + 1,
+                path.display()
+            ))
+        })
+        .collect()
+}
+
+/// Run the command, returning what it printed if it succeeded
+fn output(command: &mut Command) -> Result<String, String> {
+    let output = command
+        .output()
+        .map_err(|err| format!("could not run {:?}: {}", command, err))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    } else {
+        Err(format!(
+            "{:?} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Build the revision in its own worktree and run its benchmarks there. The worktree is kept
+/// around so that comparing against the revision again doesn't rebuild it from scratch.
+fn run_revision(revision: &str, options: &Options) -> Result<Vec<Measurement>, String> {
+    let commit = output(Command::new("git").args([
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{}^{{commit}}", revision),
+    ]))
+    .map_err(|_| format!("{:?} is neither a saved file nor a git revision", revision))?;
+    let root = PathBuf::from(output(
+        Command::new("git").args(["rev-parse", "--show-toplevel"]),
+    )?);
+
+    let worktree = root.join("target").join("bench").join(&commit);
+    if !worktree.exists() {
+        // Forget the worktrees of a `target` directory that's since been cleaned
+        output(Command::new("git").args(["worktree", "prune"]))?;
+        output(
+            Command::new("git")
+                .args(["worktree", "add", "--detach"])
+                .arg(&worktree)
+                .arg(&commit),
+        )?;
+    }
+
+    eprintln!("building {} ({})", revision, &commit[..12]);
+    let built = Command::new("cargo")
+        .args(["build", "--release", "--quiet", "--bin", "aoc-bench"])
+        .current_dir(&worktree)
+        .status()
+        .map_err(|err| format!("could not run cargo: {}", err))?;
+    if !built.success() {
+        return Err(format!("aoc-bench couldn't be built at {}", revision));
+    }
+
+    let inputs = fs::canonicalize(common::inputs())
+        .map_err(|err| format!("could not find the inputs: {}", err))?;
+    let saved = worktree.join("target").join("bench.tsv");
+    let _ = fs::remove_file(&saved);
+
+    eprintln!("benchmarking {}", revision);
+    Command::new(worktree.join("target").join("release").join("aoc-bench"))
+        .arg("--time")
+        .arg(options.time.as_millis().to_string())
+        .arg("--save")
+        .arg(&saved)
+        .arg(&options.filter)
+        .env("AOC_INPUTS", inputs)
+        .current_dir(&worktree)
+        .status()
+        .map_err(|err| format!("could not run aoc-bench at {}: {}", revision, err))?;
+
+    // The benchmarks which failed are left out of the file, and show up as removed or added when
+    // the sides are compared
+    load(&saved)
+}
+
+/// The times for one side of a comparison, which is either a file written by `--save` or a git
+/// revision
+fn side(side: &str, options: &Options) -> Result<Vec<Measurement>, String> {
+    if Path::new(side).is_file() {
+        load(Path::new(side))
+    } else {
+        run_revision(side, options)
+    }
+}
+
+/// Print how each benchmark's median changed between the sides, returning whether any got slower
+/// by more than the threshold
+fn compare(old: &[Measurement], new: &[Measurement], threshold: f64) -> bool {
+    let mut names = new.iter().map(|new| &new.name).collect::<Vec<_>>();
+    names.extend(
+        old.iter()
+            .map(|old| &old.name)
+            .filter(|name| !new.iter().any(|new| new.name == **name)),
+    );
+
+    let mut regressed = false;
+
+    println!();
+    print_row("benchmark", "change", "old", "new", "");
+    for name in names {
+        let find = |measurements: &[Measurement]| {
+            measurements
+                .iter()
+                .find(|measurement| measurement.name == *name)
+                .map(|measurement| measurement.median)
+        };
+
+        let (change, note) = match (find(old), find(new)) {
+            (Some(old), Some(new)) => {
+                let change = (new.as_secs_f64() / old.as_secs_f64() - 1.0) * 100.0;
+                let note = if change > threshold {
+                    regressed = true;
+                    "slower"
+                } else if change < -threshold {
+                    "faster"
+                } else {
+                    ""
+                };
+                (format!("{:+.1}%", change), note)
+            }
+
+            (Some(_), None) => (String::new(), "removed"),
+            (None, _) => (String::new(), "added"),
+        };
+
+        let median = |median: Option<Duration>| {
+            median.map_or_else(String::new, |median| format!("{:.2?}", median))
+        };
+        print_row(name, &change, &median(find(old)), &median(find(new)), note);
+    }
+
+    regressed
+}
+
+fn main() {
+    let options = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    if let Some((old, new)) = &options.compare {
+        let sides = side(old, &options).and_then(|old| Ok((old, side(new, &options)?)));
+        match sides {
+            Ok((old, new)) => {
+                if compare(&old, &new, options.threshold) {
+                    process::exit(1);
+                }
+            }
+
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let filter = options.filter.clone();
+    let time = options.time;
+    let (measurements, failed) = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || run(&filter, time))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    if let Some(path) = &options.save {
+        if let Err(err) = save(path, &measurements) {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,126 @@
+//! Every day's solver, run by the `aoc` binary, timed by `aoc-bench` and checked against the stored
+//! answers by the tests
+
+pub mod bench;
+
+use std::time::{Duration, Instant};
+
+use animation::Screen;
+use common::Solution;
+
+/// Every day's solver, by day
+pub const DAYS: [fn(Option<u32>) -> Report; 25] = [
+    solve::<day01::Day01>,
+    solve::<day02::Day02>,
+    solve::<day03::Day03>,
+    solve::<day04::Day04>,
+    solve::<day05::Day05>,
+    solve::<day06::Day06>,
+    solve::<day07::Day07>,
+    solve::<day08::Day08>,
+    solve::<day09::Day09>,
+    solve::<day10::Day10>,
+    solve::<day11::Day11>,
+    solve::<day12::Day12>,
+    solve::<day13::Day13>,
+    solve::<day14::Day14>,
+    solve::<day15::Day15>,
+    solve::<day16::Day16>,
+    solve::<day17::Day17>,
+    solve::<day18::Day18>,
+    solve::<day19::Day19>,
+    solve::<day20::Day20>,
+    solve::<day21::Day21>,
+    solve::<day22::Day22>,
+    solve::<day23::Day23>,
+    solve::<day24::Day24>,
+    solve::<day25::Day25>,
+];
+
+/// Animate the day's puzzle for the input, returning the answer to its second part, which is what
+/// it plays out to
+pub fn animate(day: u32, input: &str, screen: &mut dyn Screen) -> common::Result<String> {
+    match day {
+        11 => day11::Day11::parse(input)?.animate(screen),
+        13 => day13::Day13::parse(input)?.animate(screen),
+        15 => day15::Day15::parse(input)?.animate(screen),
+        17 => day17::Day17::parse(input)?.animate(screen),
+        24 => day24::Day24::parse(input)?.animate(screen),
+        _ => Err(format!("day {} can't be animated", day).into()),
+    }
+}
+
+/// The colors of the tiles of the days which can be recorded into GIFs
+pub fn colors(day: u32) -> Option<&'static [[u8; 3]]> {
+    match day {
+        13 => Some(&day13::COLORS),
+        15 => Some(&day15::COLORS),
+        24 => Some(&day24::COLORS),
+        _ => None,
+    }
+}
+
+/// A part's answer and how long it took to find
+pub struct Answer {
+    pub part: u32,
+    pub answer: common::Result<String>,
+    pub time: Duration,
+}
+
+/// How solving a day went. A day whose input couldn't be read or parsed has no answers.
+pub struct Report {
+    pub day: u32,
+    pub parsed: common::Result<Duration>,
+    pub answers: Vec<Answer>,
+}
+
+impl Report {
+    pub fn failed(&self) -> bool {
+        self.parsed.is_err() || self.answers.iter().any(|answer| answer.answer.is_err())
+    }
+}
+
+/// Solve the day's puzzle for its stored input, timing the parsing and each part, which is every
+/// part if none is given
+pub fn solve<S: Solution>(part: Option<u32>) -> Report {
+    let mut report = Report {
+        day: S::DAY,
+        parsed: Ok(Duration::default()),
+        answers: Vec::new(),
+    };
+
+    let input = match common::stored_input(S::DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            report.parsed = Err(err);
+            return report;
+        }
+    };
+
+    let start = Instant::now();
+    let solution = match S::parse(&input) {
+        Ok(solution) => solution,
+        Err(err) => {
+            report.parsed = Err(err);
+            return report;
+        }
+    };
+    report.parsed = Ok(start.elapsed());
+
+    let parts = match part {
+        Some(part) => part..=part,
+        None => 1..=S::PARTS,
+    };
+
+    for part in parts {
+        let start = Instant::now();
+        let answer = solution.part(part);
+        report.answers.push(Answer {
+            part,
+            answer,
+            time: start.elapsed(),
+        });
+    }
+
+    report
+}
@@ -0,0 +1,187 @@
+use std::{
+    env, fmt,
+    fmt::Write as _,
+    process,
+    time::{Duration, Instant},
+};
+
+use aoc::{Report, DAYS};
+
+const USAGE: &str = "usage: aoc [--json] [<day> [<part>]]
+
+Solves every day's puzzle, or just the given day's or part's, printing each answer with how long it
+took. Inputs are read from the inputs directory, or from $AOC_INPUTS if it's set.
+
+options:
+    --json  print the answers and timings as JSON";
+
+struct Options {
+    json: bool,
+    day: Option<u32>,
+    part: Option<u32>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        json: false,
+        day: None,
+        part: None,
+    };
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => options.json = true,
+
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+
+            _ if options.day.is_none() => {
+                let day = arg
+                    .parse()
+                    .ok()
+                    .filter(|day| (1..=DAYS.len() as u32).contains(day))
+                    .ok_or(format!("{:?} isn't a day of Advent", arg))?;
+                options.day = Some(day);
+            }
+
+            _ if options.part.is_none() => {
+                let part = arg
+                    .parse()
+                    .ok()
+                    .filter(|&part| part != 0)
+                    .ok_or(format!("{:?} isn't a part", arg))?;
+                options.part = Some(part);
+            }
+
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// How far into a row of the table its answer starts
+const ANSWER_COLUMN: usize = 3 + 2 + 5 + 2 + 12 + 2;
+
+fn print_row(day: &dyn fmt::Display, part: &dyn fmt::Display, time: &dyn fmt::Display, text: &str) {
+    let row = format!("{:>3}  {:<5}  {:>12}  {}", day, part, time, text);
+    println!("{}", row.trim_end());
+}
+
+/// Print the reports as a table, with answers spanning several lines, like images, lined up under
+/// each other
+fn print_table(reports: &[Report], total: Duration) {
+    print_row(&"day", &"part", &"time", "answer");
+
+    for report in reports {
+        match &report.parsed {
+            Ok(time) => print_row(&report.day, &"parse", &format!("{:.2?}", time), ""),
+            Err(err) => print_row(&report.day, &"parse", &"", &format!("error: {}", err)),
+        }
+
+        for answer in &report.answers {
+            let text = match &answer.answer {
+                Ok(answer) => answer.replace('\n', &format!("\n{:1$}", "", ANSWER_COLUMN)),
+                Err(err) => format!("error: {}", err),
+            };
+
+            print_row(
+                &report.day,
+                &answer.part,
+                &format!("{:.2?}", answer.time),
+                &text,
+            );
+        }
+    }
+
+    print_row(&"", &"total", &format!("{:.2?}", total), "");
+}
+
+/// Write the string as a JSON string literal
+fn json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn json_error(out: &mut String, err: &impl fmt::Display) {
+    out.push_str("\"error\": ");
+    json_string(out, &err.to_string());
+}
+
+/// Print the reports as a JSON object, with times in nanoseconds
+fn print_json(reports: &[Report], total: Duration) {
+    let mut out = String::from("{\"days\": [");
+
+    for (idx, report) in reports.iter().enumerate() {
+        if idx != 0 {
+            out.push(',');
+        }
+        write!(out, "\n  {{\"day\": {}, ", report.day).unwrap();
+
+        match &report.parsed {
+            Ok(time) => write!(out, "\"parse_ns\": {}, ", time.as_nanos()).unwrap(),
+            Err(err) => {
+                json_error(&mut out, err);
+                out.push_str(", ");
+            }
+        }
+
+        out.push_str("\"parts\": [");
+        for (idx, answer) in report.answers.iter().enumerate() {
+            if idx != 0 {
+                out.push_str(", ");
+            }
+            write!(out, "{{\"part\": {}, ", answer.part).unwrap();
+
+            match &answer.answer {
+                Ok(answer) => {
+                    out.push_str("\"answer\": ");
+                    json_string(&mut out, answer);
+                }
+                Err(err) => json_error(&mut out, err),
+            }
+
+            write!(out, ", \"ns\": {}}}", answer.time.as_nanos()).unwrap();
+        }
+        out.push_str("]}");
+    }
+
+    write!(out, "\n], \"total_ns\": {}}}", total.as_nanos()).unwrap();
+    println!("{}", out);
+}
+
+fn main() {
+    let options = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    let days = match options.day {
+        Some(day) => day..=day,
+        None => 1..=DAYS.len() as u32,
+    };
+
+    let start = Instant::now();
+    let reports = days
+        .map(|day| DAYS[day as usize - 1](options.part))
+        .collect::<Vec<_>>();
+    let total = start.elapsed();
+
+    if options.json {
+        print_json(&reports, total);
+    } else {
+        print_table(&reports, total);
+    }
+
+    if reports.iter().any(Report::failed) {
+        process::exit(1);
+    }
+}
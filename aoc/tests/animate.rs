@@ -0,0 +1,75 @@
+use std::thread;
+
+use animation::{Frame, Screen};
+use intcode::STACK_SIZE;
+
+/// A screen which only counts the frames it's shown
+struct Counter {
+    frames: usize,
+    stopped: bool,
+}
+
+impl Screen for Counter {
+    fn draw(&mut self, _: &dyn Frame) {
+        self.frames += 1;
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+}
+
+/// Watch the day's puzzle for its stored input, both drawing it and with the screen stopped as if
+/// it had been skipped, checking it plays out to the stored answer to the second part either way
+fn check(day: u32) {
+    let expected = common::stored_answer(day, 2).unwrap();
+
+    for &stopped in &[false, true] {
+        let (answer, frames) = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(move || {
+                let input = common::stored_input(day).map_err(|err| err.to_string())?;
+                let mut screen = Counter { frames: 0, stopped };
+                let answer = aoc::animate(day, &input, &mut screen).map_err(|err| err.to_string());
+                Ok::<_, String>((answer?, screen.frames))
+            })
+            .unwrap()
+            .join()
+            .unwrap()
+            .unwrap_or_else(|err| panic!("day {}: {}", day, err));
+
+        assert_eq!(answer, expected, "day {}", day);
+        if !stopped {
+            assert!(frames > 0, "day {} drew nothing", day);
+        }
+    }
+}
+
+macro_rules! days {
+    ($($name:ident = $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check($day);
+            }
+        )*
+    };
+}
+
+days! {
+    day11 = 11,
+    day13 = 13,
+    day15 = 15,
+    day17 = 17,
+    day24 = 24,
+}
+
+#[test]
+fn not_animated() {
+    let mut screen = Counter {
+        frames: 0,
+        stopped: false,
+    };
+    let err = aoc::animate(1, "", &mut screen).unwrap_err();
+    assert_eq!(err.to_string(), "day 1 can't be animated");
+}
@@ -0,0 +1,79 @@
+use std::thread;
+
+use intcode::STACK_SIZE;
+
+/// Solve the day's puzzle and compare every part's answer to the stored one, reporting each part
+/// that differs
+fn check(day: u32) {
+    let report = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || aoc::DAYS[day as usize - 1](None))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    if let Err(err) = &report.parsed {
+        panic!("day {}: {}", day, err);
+    }
+
+    let mut wrong = Vec::new();
+    for answer in &report.answers {
+        let expected = match common::stored_answer(day, answer.part) {
+            Ok(expected) => expected,
+            Err(err) => {
+                wrong.push(format!("day {} part {}: {}", day, answer.part, err));
+                continue;
+            }
+        };
+
+        match &answer.answer {
+            Ok(answer) if *answer == expected => {}
+            Ok(got) => wrong.push(format!(
+                "day {} part {}: expected\n{}\nbut got\n{}",
+                day, answer.part, expected, got
+            )),
+            Err(err) => wrong.push(format!("day {} part {}: {}", day, answer.part, err)),
+        }
+    }
+
+    assert!(wrong.is_empty(), "{}", wrong.join("\n"));
+}
+
+macro_rules! days {
+    ($($name:ident = $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check($day);
+            }
+        )*
+    };
+}
+
+days! {
+    day01 = 1,
+    day02 = 2,
+    day03 = 3,
+    day04 = 4,
+    day05 = 5,
+    day06 = 6,
+    day07 = 7,
+    day08 = 8,
+    day09 = 9,
+    day10 = 10,
+    day11 = 11,
+    day12 = 12,
+    day13 = 13,
+    day14 = 14,
+    day15 = 15,
+    day16 = 16,
+    day17 = 17,
+    day18 = 18,
+    day19 = 19,
+    day20 = 20,
+    day21 = 21,
+    day22 = 22,
+    day23 = 23,
+    day24 = 24,
+    day25 = 25,
+}
@@ -0,0 +1,127 @@
+//! What every day's solver needs, loading its puzzle input, reporting what's wrong with it and
+//! answering each of its parts
+
+use std::{
+    env, error, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Why a day couldn't be solved, usually because its input couldn't be read or doesn't make sense.
+/// Anything which converts into a boxed error, including plain strings, converts into one, and
+/// returning one from `main` prints its message.
+pub struct Error(Box<dyn error::Error + Send + Sync>);
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl<E: Into<Box<dyn error::Error + Send + Sync>>> From<E> for Error {
+    fn from(error: E) -> Self {
+        Self(error.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// `main` prints errors it returns with `Debug`, which is the message rather than the structure
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The directory inputs are found in when no path is given, `$AOC_INPUTS` if it's set and
+/// otherwise the workspace's `inputs` directory
+pub fn inputs() -> PathBuf {
+    match env::var_os("AOC_INPUTS") {
+        Some(inputs) => inputs.into(),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"),
+    }
+}
+
+/// A day's puzzle, parsed once from its input and then asked for the answer to each of its parts
+pub trait Solution: Sized {
+    /// The day of December the puzzle is for
+    const DAY: u32;
+
+    /// How many parts the puzzle has, two on every day but Christmas
+    const PARTS: u32 = 2;
+
+    fn parse(input: &str) -> Result<Self>;
+
+    fn part1(&self) -> Result<String>;
+
+    fn part2(&self) -> Result<String> {
+        Err(format!("day {} has no part 2", Self::DAY).into())
+    }
+
+    /// The answer to the given part, counting from one
+    fn part(&self, part: u32) -> Result<String> {
+        match part {
+            1 => self.part1(),
+            2 if Self::PARTS == 2 => self.part2(),
+            _ => Err(format!("day {} has no part {}", Self::DAY, part).into()),
+        }
+    }
+}
+
+/// Solve the puzzle for the input `input` finds, printing the answer to each part on its own line
+pub fn main<S: Solution>() -> Result<()> {
+    let solution = S::parse(&input(S::DAY)?)?;
+    for part in 1..=S::PARTS {
+        println!("{}", solution.part(part)?);
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {}", path.display(), err).into())
+}
+
+/// Load the day's puzzle input from the path given as the first command line argument, from stdin
+/// if that's `-`, and otherwise from the conventional inputs directory as `day01.txt` and so on
+pub fn input(day: u32) -> Result<String> {
+    match env::args_os().nth(1) {
+        Some(path) if path == "-" => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| format!("could not read stdin: {}", err))?;
+            Ok(input)
+        }
+
+        Some(path) => read(Path::new(&path)),
+        None => stored_input(day),
+    }
+}
+
+/// Load the day's puzzle input from the inputs directory, ignoring the command line
+pub fn stored_input(day: u32) -> Result<String> {
+    read(&inputs().join(format!("day{:02}.txt", day)))
+}
+
+/// Load the known answer to a part of the day's puzzle for its stored input, which lives next to
+/// the input as `day01.part1.txt` and so on
+pub fn stored_answer(day: u32, part: u32) -> Result<String> {
+    let mut answer = read(&inputs().join(format!("day{:02}.part{}.txt", day, part)))?;
+    answer.truncate(answer.trim_end_matches('\n').len());
+    Ok(answer)
+}
+
+/// Parse each line of the input but the blank ones at its end, adding which line was wrong to any
+/// error
+pub fn parse_lines<'a, T, E: fmt::Display>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, E>,
+) -> Result<Vec<T>> {
+    input
+        .trim_end()
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse(line).map_err(|err| format!("line {}: {}", idx + 1, err).into()))
+        .collect()
+}
@@ -0,0 +1,209 @@
+//! Finding where a simulation starts repeating itself. A simulation is a state and a step from each
+//! state to the next, and since the step only looks at the state, a state it's been in before is
+//! followed by the same states it was followed by the first time round.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the states a simulation goes through start repeating
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<T> {
+    /// How many steps it takes to get to the first state which is repeated
+    pub start: usize,
+
+    /// How many steps it takes to get back to a state once it's in the cycle
+    pub length: usize,
+
+    /// The first state which is repeated, after `start` steps
+    pub state: T,
+}
+
+impl<T> Cycle<T> {
+    /// How many steps after `start` into the cycle the simulation is after some steps, if it's got
+    /// to the cycle by then. Doing this many steps from `state` gets to the same state as doing all
+    /// of them from the start.
+    pub fn offset(&self, steps: usize) -> Option<usize> {
+        steps
+            .checked_sub(self.start)
+            .map(|steps| steps % self.length)
+    }
+}
+
+/// Find the cycle with Floyd's tortoise and hare, which only needs to keep two states around but
+/// steps through the states about three times over
+pub fn floyd<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle<T> {
+    // The hare moves twice as fast as the tortoise, so they meet once both are in the cycle and the
+    // hare has lapped the tortoise, after a multiple of the cycle's length
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    // The tortoise is as many steps from the cycle's start as the initial state is, plus some laps
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle {
+        start,
+        length,
+        state: tortoise,
+    }
+}
+
+/// Find the cycle with Brent's algorithm, which only needs to keep two states around and usually
+/// takes fewer steps than Floyd's
+pub fn brent<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle<T> {
+    // Leave the tortoise at each power of two for the hare to catch up to, which it does once the
+    // power of two's past the cycle's start and longer than the cycle
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare a cycle's length ahead of the tortoise, they meet at the cycle's start
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle {
+        start,
+        length,
+        state: tortoise,
+    }
+}
+
+/// Find the cycle by remembering every state, which steps through each one only once but needs to
+/// keep them all around
+pub fn hashed<T: Clone + Eq + Hash>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle<T> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for steps in 0.. {
+        if let Some(start) = seen.insert(state.clone(), steps) {
+            return Cycle {
+                start,
+                length: steps - start,
+                state,
+            };
+        }
+        state = step(&state);
+    }
+
+    unreachable!("a simulation can't go through more states than there are steps")
+}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// The least common multiple of two numbers, which is how often two cycles of those lengths both get
+/// back to where they started
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A sequence going 3, 1, 4, 10, 5, 9, 2, 6 and then back to 5 forever
+    fn digits(&n: &u8) -> u8 {
+        match n {
+            3 => 1,
+            1 => 4,
+            4 => 10,
+            10 => 5,
+            5 => 9,
+            9 => 2,
+            2 => 6,
+            6 => 5,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn finders_agree() {
+        let expected = Cycle {
+            start: 4,
+            length: 4,
+            state: 5,
+        };
+
+        assert_eq!(floyd(3, digits), expected);
+        assert_eq!(brent(3, digits), expected);
+        assert_eq!(hashed(3, digits), expected);
+    }
+
+    #[test]
+    fn cycles_from_the_start() {
+        let expected = Cycle {
+            start: 0,
+            length: 7,
+            state: 0,
+        };
+        let step = |&n: &u32| (n + 1) % 7;
+
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(hashed(0, step), expected);
+
+        let constant = brent('x', |&c| c);
+        assert_eq!((constant.start, constant.length), (0, 1));
+    }
+
+    #[test]
+    fn offset() {
+        let cycle = hashed(3, digits);
+        assert_eq!(cycle.offset(2), None);
+        assert_eq!(cycle.offset(4), Some(0));
+        assert_eq!(cycle.offset(13), Some(1));
+    }
+
+    #[test]
+    fn lcm_of_periods() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(7, 0), 0);
+        assert_eq!([18, 28, 44].iter().fold(1, |n, &m| lcm(n, m)), 2772);
+    }
+}
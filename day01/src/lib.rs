@@ -0,0 +1,70 @@
+use common::Solution;
+
+mod first {
+    pub fn fuel(mass: isize) -> isize {
+        mass / 3 - 2
+    }
+}
+
+mod second {
+    pub fn fuel(mass: isize) -> isize {
+        use super::first;
+        std::iter::successors(Some(first::fuel(mass)), |&f| Some(first::fuel(f)))
+            .take_while(|&n| n > 0)
+            .sum()
+    }
+}
+
+pub struct Day01 {
+    modules: Vec<isize>,
+}
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            modules: common::parse_lines(input, str::parse)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self
+            .modules
+            .iter()
+            .copied()
+            .map(first::fuel)
+            .sum::<isize>()
+            .to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(self
+            .modules
+            .iter()
+            .copied()
+            .map(second::fuel)
+            .sum::<isize>()
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuel_examples() {
+        assert_eq!(first::fuel(12), 2);
+        assert_eq!(first::fuel(14), 2);
+        assert_eq!(first::fuel(1969), 654);
+        assert_eq!(first::fuel(100756), 33583);
+    }
+
+    #[test]
+    fn fuel_for_fuel_examples() {
+        assert_eq!(second::fuel(14), 2);
+        assert_eq!(second::fuel(1969), 966);
+        assert_eq!(second::fuel(100756), 50346);
+    }
+}
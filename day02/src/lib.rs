@@ -0,0 +1,56 @@
+use common::Solution;
+use intcode::{symbolic::Executor, Interpreter};
+
+const TARGET: i64 = 19690720;
+
+pub struct Day02 {
+    interpreter: Interpreter,
+}
+
+impl Day02 {
+    fn run(&self, noun: i64, verb: i64) -> i64 {
+        let mut interpreter = self.interpreter.clone();
+        interpreter.memory[1] = noun;
+        interpreter.memory[2] = verb;
+        interpreter.run();
+        interpreter.memory[0]
+    }
+}
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            interpreter: Interpreter::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.run(12, 2).to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        // Rather than searching every noun and verb, work out the output as a formula of them and
+        // solve that
+        let mut executor = Executor::new(&self.interpreter.memory);
+        executor.unknown_cell(1, "noun", 0..100);
+        executor.unknown_cell(2, "verb", 0..100);
+
+        let solution = executor
+            .solve(TARGET, |executor| executor.memory[0].clone())
+            .ok_or("no noun and verb give the target")?;
+        Ok((100 * solution[0] + solution[1]).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let day = Day02::parse("1,9,10,3,2,3,11,0,99,30,40,50").unwrap();
+        assert_eq!(day.run(9, 10), 3500);
+    }
+}
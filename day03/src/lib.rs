@@ -0,0 +1,154 @@
+use std::{cmp::min, convert::TryFrom};
+
+use common::Solution;
+use grid::{Direction, Point};
+
+/// A straight stretch of wire, which is `idx` steps along the wire from its start
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Segment {
+    start: Point<i32>,
+    end: Point<i32>,
+    idx: i32,
+}
+
+type Wire = (Vec<Segment>, Vec<Segment>);
+
+fn points(path: &str) -> Result<Wire, String> {
+    let mut horizontal = Vec::new();
+    let mut vertical = Vec::new();
+
+    let mut position = Point::ORIGIN;
+    let mut idx = 0;
+
+    for step in path.split(',') {
+        let mut chars = step.chars();
+
+        let direction = chars.next().ok_or("empty step")?;
+        let direction = Direction::parse(direction)
+            .filter(|_| direction.is_ascii_uppercase())
+            .ok_or_else(|| format!("{:?} isn't a direction", direction))?;
+        let length: i32 = chars
+            .as_str()
+            .parse()
+            .map_err(|_| format!("{:?} isn't a step", step))?;
+
+        let segment = Segment {
+            start: position,
+            end: position
+                .checked_steps(direction, length)
+                .ok_or("the wire goes too far")?,
+            idx,
+        };
+
+        match direction {
+            Direction::Up | Direction::Down => vertical.push(segment),
+            Direction::Left | Direction::Right => horizontal.push(segment),
+        }
+
+        position = segment.end;
+        idx += length;
+    }
+
+    Ok((horizontal, vertical))
+}
+
+fn intersect_1d(l: i32, h: i32, x: i32) -> bool {
+    (x >= l && x <= h) || (x >= h && x <= l)
+}
+
+fn intersect(horizontal: Segment, vertical: Segment) -> Option<Point<i32>> {
+    if intersect_1d(horizontal.start.x, horizontal.end.x, vertical.start.x)
+        && intersect_1d(vertical.start.y, vertical.end.y, horizontal.start.y)
+    {
+        Some(Point::new(vertical.start.x, horizontal.start.y))
+    } else {
+        None
+    }
+}
+
+fn solve(horizontal: &[Segment], vertical: &[Segment]) -> (i32, i32) {
+    let mut part1 = i32::MAX;
+    let mut part2 = i32::MAX;
+
+    for &horizontal in horizontal {
+        for &vertical in vertical {
+            if let Some(crossing) = intersect(horizontal, vertical) {
+                if crossing != Point::ORIGIN {
+                    part1 = min(part1, crossing.manhattan(Point::ORIGIN));
+
+                    let steps1 = horizontal.idx + crossing.manhattan(horizontal.start);
+                    let steps2 = vertical.idx + crossing.manhattan(vertical.start);
+                    part2 = min(part2, steps1 + steps2);
+
+                    break;
+                }
+            }
+        }
+    }
+
+    (part1, part2)
+}
+
+pub struct Day03 {
+    wire1: Wire,
+    wire2: Wire,
+}
+
+impl Day03 {
+    /// The closest intersection's distance from the origin and the fewest steps to an intersection
+    fn solve(&self) -> (i32, i32) {
+        let (wire1_horizontal, wire1_vertical) = &self.wire1;
+        let (wire2_horizontal, wire2_vertical) = &self.wire2;
+
+        let (part1_1, part2_1) = solve(wire1_horizontal, wire2_vertical);
+        let (part1_2, part2_2) = solve(wire2_horizontal, wire1_vertical);
+
+        (min(part1_1, part1_2), min(part2_1, part2_2))
+    }
+}
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let wires = common::parse_lines(input, points)?;
+        match <[_; 2]>::try_from(wires) {
+            Ok([wire1, wire2]) => Ok(Self { wire1, wire2 }),
+            Err(wires) => Err(format!("expected 2 wires but got {}", wires.len()).into()),
+        }
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.solve().0.to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(self.solve().1.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(wires: &str) -> (String, String) {
+        let day = Day03::parse(wires).unwrap();
+        (day.part1().unwrap(), day.part2().unwrap())
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(solve("R8,U5,L5,D3\nU7,R6,D4,L4"), ("6".into(), "30".into()));
+        assert_eq!(
+            solve("R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83"),
+            ("159".into(), "610".into())
+        );
+        assert_eq!(
+            solve(
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\n\
+                 U98,R91,D20,R16,D67,R40,U7,R15,U6,R7"
+            ),
+            ("135".into(), "410".into())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use common::Solution;
+
+pub struct Day04 {
+    low: usize,
+    high: usize,
+}
+
+impl Day04 {
+    /// How many passwords in the range have a double digit, and how many have an exact pair
+    fn count(&self) -> (usize, usize) {
+        let Self { low, high } = *self;
+
+        let mut part1 = 0;
+        let mut part2 = 0;
+
+        for a in 0..=9 {
+            for b in a..=9 {
+                for c in b..=9 {
+                    for d in c..=9 {
+                        for e in d..=9 {
+                            for f in e..=9 {
+                                let n = a * 100_000 + b * 10000 + c * 1000 + d * 100 + e * 10 + f;
+                                if n < low || n > high {
+                                    continue;
+                                }
+
+                                let mut counts = [0; 10];
+                                counts[a] += 1;
+                                counts[b] += 1;
+                                counts[c] += 1;
+                                counts[d] += 1;
+                                counts[e] += 1;
+                                counts[f] += 1;
+
+                                if counts.iter().copied().any(|n| n >= 2) {
+                                    part1 += 1;
+                                }
+
+                                if counts.iter().copied().any(|n| n == 2) {
+                                    part2 += 1;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        (part1, part2)
+    }
+}
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let (low, high) = input
+            .trim()
+            .split_once('-')
+            .ok_or("expected a range like 123456-654321")?;
+        Ok(Self {
+            low: low.parse()?,
+            high: high.parse()?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.count().0.to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(self.count().1.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the password meets each part's criteria, as a range of just it
+    fn valid(password: &str) -> (bool, bool) {
+        let day = Day04::parse(&format!("{}-{}", password, password)).unwrap();
+        let (part1, part2) = day.count();
+        (part1 == 1, part2 == 1)
+    }
+
+    #[test]
+    fn examples() {
+        assert!(valid("111111").0);
+        assert!(!valid("223450").0);
+        assert!(!valid("123789").0);
+
+        assert!(valid("112233").1);
+        assert!(!valid("123444").1);
+        assert!(valid("111122").1);
+    }
+}
@@ -0,0 +1,65 @@
+use common::Solution;
+use intcode::Interpreter;
+
+pub struct Day05 {
+    interpreter: Interpreter,
+}
+
+impl Day05 {
+    fn run(&self, input: i64) -> common::Result<String> {
+        let mut interpreter = self.interpreter.clone();
+        interpreter.input.push_back(input);
+        interpreter.run();
+        Ok(interpreter
+            .output
+            .pop_back()
+            .ok_or("the diagnostic program gave no output")?
+            .to_string())
+    }
+}
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            interpreter: Interpreter::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        self.run(1)
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        self.run(5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparison_examples() {
+        let equal_to_8 = Day05::parse("3,9,8,9,10,9,4,9,99,-1,8").unwrap();
+        assert_eq!(equal_to_8.run(8).unwrap(), "1");
+        assert_eq!(equal_to_8.run(7).unwrap(), "0");
+
+        let less_than_8 = Day05::parse("3,3,1107,-1,8,3,4,3,99").unwrap();
+        assert_eq!(less_than_8.run(7).unwrap(), "1");
+        assert_eq!(less_than_8.run(8).unwrap(), "0");
+    }
+
+    #[test]
+    fn jump_example() {
+        let day = Day05::parse(
+            "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,\
+             4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99",
+        )
+        .unwrap();
+        assert_eq!(day.run(7).unwrap(), "999");
+        assert_eq!(day.run(8).unwrap(), "1000");
+        assert_eq!(day.run(9).unwrap(), "1001");
+    }
+}
@@ -0,0 +1,109 @@
+use std::{collections::HashMap, iter};
+
+use common::Solution;
+
+#[derive(Default)]
+struct Solver<'a> {
+    // for part 1
+    open: HashMap<&'a str, Vec<&'a str>>,
+    ranks: HashMap<&'a str, usize>,
+    total: usize,
+
+    // for part 2
+    parents: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Solver<'a> {
+    fn add_orbit(&mut self, orbited: &'a str, orbiter: &'a str) {
+        self.parents.insert(orbiter, orbited);
+
+        if let Some(rank) = self.ranks.get(orbited) {
+            let rank = rank + 1;
+            self.ranks.insert(orbiter, rank);
+            self.total += rank;
+
+            if let Some(pending) = self.open.remove(orbiter) {
+                pending
+                    .into_iter()
+                    .for_each(|orbiter2| self.add_orbit(orbiter, orbiter2));
+            }
+        } else {
+            self.open.entry(orbited).or_default().push(orbiter);
+        }
+    }
+}
+
+pub struct Day06 {
+    total: usize,
+    parents: HashMap<String, String>,
+}
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let orbits = common::parse_lines(input, |line| {
+            line.split_once(')')
+                .ok_or_else(|| format!("{:?} isn't an orbit", line))
+        })?;
+
+        let mut solver = Solver::default();
+        solver.ranks.insert("COM", 0);
+
+        orbits
+            .into_iter()
+            .for_each(|(orbited, orbiter)| solver.add_orbit(orbited, orbiter));
+
+        Ok(Self {
+            total: solver.total,
+            parents: solver
+                .parents
+                .into_iter()
+                .map(|(orbiter, orbited)| (orbiter.to_owned(), orbited.to_owned()))
+                .collect(),
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.total.to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        let parents = |start| {
+            iter::successors(Some(start), |&cur| {
+                self.parents.get(cur).map(String::as_str)
+            })
+        };
+
+        let chain = parents("YOU")
+            .enumerate()
+            .map(|(v, k)| (k, v))
+            .collect::<HashMap<_, _>>();
+
+        let (dist, common) = parents("SAN")
+            .enumerate()
+            .find(|(_, node)| chain.contains_key(node))
+            .ok_or("you and Santa aren't orbiting anything in common")?;
+
+        Ok(((dist - 1) + (chain[common] - 1)).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let day = Day06::parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L").unwrap();
+        assert_eq!(day.part1().unwrap(), "42");
+    }
+
+    #[test]
+    fn part2_example() {
+        let day =
+            Day06::parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN")
+                .unwrap();
+        assert_eq!(day.part2().unwrap(), "4");
+    }
+}
@@ -0,0 +1,146 @@
+use common::Solution;
+use intcode::{batch, Interpreter};
+
+type Phases = (i64, i64, i64, i64, i64);
+
+fn amplify(interpreter: &Interpreter, (a, b, c, d, e): Phases) -> i64 {
+    let mut interpreters = [
+        interpreter.clone(),
+        interpreter.clone(),
+        interpreter.clone(),
+        interpreter.clone(),
+        interpreter.clone(),
+    ];
+
+    interpreters[0].input.push_back(a);
+    interpreters[1].input.push_back(b);
+    interpreters[2].input.push_back(c);
+    interpreters[3].input.push_back(d);
+    interpreters[4].input.push_back(e);
+
+    interpreters[0].input.push_back(0);
+
+    while !interpreters.last().unwrap().done {
+        for i in 0..interpreters.len() {
+            interpreters[i].run();
+
+            if let Some(output) = interpreters[i].output.pop_front() {
+                interpreters[(i + 1) % interpreters.len()]
+                    .input
+                    .push_back(output);
+            }
+        }
+    }
+
+    interpreters[0].input[0]
+}
+
+fn for_all_phases(left: i64, right: i64, mut f: impl FnMut(Phases)) {
+    for a in left..=right {
+        for b in left..=right {
+            if b == a {
+                continue;
+            }
+            for c in left..=right {
+                if !(c != a && c != b) {
+                    continue;
+                }
+                for d in left..=right {
+                    if !(d != a && d != b && d != c) {
+                        continue;
+                    }
+                    for e in left..=right {
+                        if !(e != a && e != b && e != c && e != d) {
+                            continue;
+                        }
+
+                        f((a, b, c, d, e))
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn largest_output(interpreter: &Interpreter, low: i64, high: i64) -> i64 {
+    let mut all_phases = Vec::new();
+    for_all_phases(low, high, |phases| all_phases.push(phases));
+
+    let jobs = all_phases
+        .into_iter()
+        .map(|phases| move |interpreter: Interpreter| amplify(&interpreter, phases));
+    batch::run(interpreter, jobs).into_iter().max().unwrap()
+}
+
+pub struct Day07 {
+    interpreter: Interpreter,
+}
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            interpreter: Interpreter::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(largest_output(&self.interpreter, 0, 4).to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(largest_output(&self.interpreter, 5, 9).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part1(program: &str) -> String {
+        Day07::parse(program).unwrap().part1().unwrap()
+    }
+
+    fn part2(program: &str) -> String {
+        Day07::parse(program).unwrap().part2().unwrap()
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(
+            part1("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0"),
+            "43210"
+        );
+        assert_eq!(
+            part1("3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0"),
+            "54321"
+        );
+        assert_eq!(
+            part1(
+                "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,\
+                 31,31,4,31,99,0,0,0"
+            ),
+            "65210"
+        );
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(
+            part2(
+                "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,\
+                 0,0,5"
+            ),
+            "139629729"
+        );
+        assert_eq!(
+            part2(
+                "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,\
+                 12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,\
+                 99,0,0,0,0,10"
+            ),
+            "18216"
+        );
+    }
+}
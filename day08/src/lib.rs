@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use common::Solution;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+const WHITE: u8 = b'1';
+const TRANSPARENT: u8 = b'2';
+
+fn count(arr: &[u8]) -> BTreeMap<u8, usize> {
+    let mut result = BTreeMap::new();
+
+    arr.iter()
+        .copied()
+        .for_each(|n| *result.entry(n).or_default() += 1);
+
+    result
+}
+
+pub struct Day08 {
+    pixels: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Day08 {
+    /// Parse an image of the given size, which the puzzle's examples are smaller than the real
+    /// image
+    fn with_size(input: &str, width: usize, height: usize) -> common::Result<Self> {
+        let pixels = input.trim().as_bytes();
+        if let Some(&pixel) = pixels.iter().find(|pixel| !pixel.is_ascii_digit()) {
+            return Err(format!("{:?} isn't a pixel", pixel as char).into());
+        }
+        if pixels.is_empty() || !pixels.len().is_multiple_of(width * height) {
+            return Err(format!(
+                "the image has {} pixels, which isn't a whole number of {}x{} layers",
+                pixels.len(),
+                width,
+                height
+            )
+            .into());
+        }
+
+        Ok(Self {
+            pixels: pixels.to_vec(),
+            width,
+            height,
+        })
+    }
+
+    fn layers(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.pixels.chunks(self.width * self.height)
+    }
+}
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Self::with_size(input, WIDTH, HEIGHT)
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        let digits = |layer: &BTreeMap<u8, usize>, digit| layer.get(&digit).copied().unwrap_or(0);
+        let layer = self
+            .layers()
+            .map(count)
+            .min_by_key(|layer| digits(layer, b'0'))
+            .unwrap();
+
+        Ok((digits(&layer, b'1') * digits(&layer, b'2')).to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        let mut image = vec![TRANSPARENT; self.width * self.height];
+
+        self.layers().for_each(|layer| {
+            layer.iter().copied().enumerate().for_each(|(i, pixel)| {
+                if image[i] == TRANSPARENT {
+                    image[i] = pixel;
+                }
+            })
+        });
+
+        Ok(image
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|&col| match col {
+                        WHITE => '█',
+                        _ => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let day = Day08::with_size("123456789012", 3, 2).unwrap();
+        assert_eq!(day.part1().unwrap(), "1");
+    }
+
+    #[test]
+    fn part2_example() {
+        let day = Day08::with_size("0222112222120000", 2, 2).unwrap();
+        assert_eq!(day.part2().unwrap(), " █\n█ ");
+    }
+}
@@ -0,0 +1,67 @@
+use common::Solution;
+use intcode::Interpreter;
+
+pub struct Day09 {
+    interpreter: Interpreter,
+}
+
+impl Day09 {
+    fn run(&self, input: i64) -> common::Result<String> {
+        let mut interpreter = self.interpreter.clone();
+        interpreter.input.push_back(input);
+        interpreter.memory.resize(interpreter.required_memory(), 0);
+        interpreter.run();
+        Ok(interpreter
+            .output
+            .front()
+            .ok_or("the BOOST program gave no output")?
+            .to_string())
+    }
+}
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            interpreter: Interpreter::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        self.run(1)
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        self.run(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quine() {
+        let program = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let mut interpreter = Day09::parse(program).unwrap().interpreter;
+        interpreter.memory.resize(interpreter.required_memory(), 0);
+        interpreter.run();
+
+        let output = interpreter
+            .output
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(output.join(","), program);
+    }
+
+    #[test]
+    fn large_numbers() {
+        let day = Day09::parse("1102,34915192,34915192,7,4,7,99,0").unwrap();
+        assert_eq!(day.run(1).unwrap().len(), 16);
+
+        let day = Day09::parse("104,1125899906842624,99").unwrap();
+        assert_eq!(day.run(1).unwrap(), "1125899906842624");
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    collections::{BinaryHeap, HashMap},
+    f64::consts::{FRAC_PI_2, PI},
+    hash::{Hash, Hasher},
+    hint::unreachable_unchecked,
+    iter::FromIterator,
+};
+
+use common::Solution;
+use grid::Grid;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct OrderedFloat(f64);
+
+impl Eq for OrderedFloat {}
+
+impl PartialOrd for OrderedFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self.0.partial_cmp(&other.0) {
+            Some(ordering) => ordering,
+            None => unsafe { unreachable_unchecked() },
+        }
+    }
+}
+
+impl Hash for OrderedFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+/// Calculate the clockwise angle from the positive y axis from one point to another
+fn angle_between((x1, y1): (i64, i64), (x2, y2): (i64, i64)) -> f64 {
+    let mut theta = FRAC_PI_2 - f64::atan2((y2 - y1) as f64, (x2 - x1) as f64);
+
+    if theta < 0.0 {
+        theta += 2.0 * PI;
+    }
+
+    theta
+}
+
+pub struct Day10 {
+    asteroids: Vec<(i64, i64)>,
+}
+
+impl Day10 {
+    /// The asteroid which can see the most others
+    fn station(&self) -> common::Result<(i64, i64)> {
+        Ok(self
+            .asteroids
+            .iter()
+            .copied()
+            .max_by_key(|&station| self.visible_from(station).len())
+            .ok_or("there are no asteroids")?)
+    }
+
+    /// The asteroids seen from the station by their angle from it, which for each angle is the
+    /// closest one
+    fn visible_from(&self, station: (i64, i64)) -> HashMap<OrderedFloat, (i64, i64)> {
+        let distance = |(x, y): (i64, i64)| (x - station.0).abs() + (y - station.1).abs();
+
+        let mut visible = HashMap::new();
+        for asteroid in self.asteroids.iter().copied() {
+            if asteroid == station {
+                continue;
+            }
+
+            visible
+                .entry(OrderedFloat(angle_between(station, asteroid)))
+                .and_modify(|closest: &mut (i64, i64)| {
+                    if distance(asteroid) < distance(*closest) {
+                        *closest = asteroid;
+                    }
+                })
+                .or_insert(asteroid);
+        }
+        visible
+    }
+
+    /// The asteroids the laser vaporizes in its first rotation, in order
+    fn vaporized_from(&self, station: (i64, i64)) -> Vec<(i64, i64)> {
+        // Sort the asteroids by angle to get everything in destruction order
+        BinaryHeap::from_iter(self.visible_from(station))
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(_, asteroid)| asteroid)
+            .collect()
+    }
+}
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let map = Grid::parse(input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("{:?} isn't an asteroid or empty space", c)),
+        })?;
+
+        let asteroids = map
+            .iter()
+            .filter(|&(_, &asteroid)| asteroid)
+            // invert the y coordinate to use atan2 correctly
+            .map(|(position, _)| (position.x as i64, -(position.y as i64)))
+            .collect();
+
+        Ok(Self { asteroids })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.visible_from(self.station()?).len().to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        // We only look at the first rotation because part 1's answer is always greater than 200,
+        // so the 200th asteroid vaporized is in it
+        let (x, y) = *self
+            .vaporized_from(self.station()?)
+            .get(199)
+            .ok_or("fewer than 200 asteroids can be seen from the station")?;
+        Ok((100 * x - y).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part1(map: &str) -> String {
+        Day10::parse(map).unwrap().part1().unwrap()
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1(".#..#\n.....\n#####\n....#\n...##"), "8");
+        assert_eq!(
+            part1(
+                "\
+......#.#.
+#..#.#....
+..#######.
+.#.#.###..
+.#..#.....
+..#....#.#
+#..#....#.
+.##.#..###
+##...#..#.
+.#....####"
+            ),
+            "33"
+        );
+        assert_eq!(
+            part1(
+                "\
+#.#...#.#.
+.###....#.
+.#....#...
+##.#.#.#.#
+....#.#.#.
+.##..###.#
+..#...##..
+..##....##
+......#...
+.####.###."
+            ),
+            "35"
+        );
+        assert_eq!(
+            part1(
+                "\
+.#..#..###
+####.###.#
+....###.#.
+..###.##.#
+##.##.#.#.
+....###..#
+..#.#..#.#
+#..#.#.###
+.##...##.#
+.....#.#.."
+            ),
+            "41"
+        );
+    }
+
+    #[test]
+    fn vaporization_example() {
+        let day = Day10::parse(
+            "\
+.#....#####...#..
+##...##.#####..##
+##...#...#.#####.
+..#.....#...###..
+..#.#.....#....##",
+        )
+        .unwrap();
+
+        // The map's y coordinates are inverted when parsing
+        let vaporized = day
+            .vaporized_from((8, -3))
+            .into_iter()
+            .map(|(x, y)| (x, -y))
+            .take(9)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vaporized,
+            [
+                (8, 1),
+                (9, 0),
+                (9, 1),
+                (10, 0),
+                (9, 2),
+                (11, 1),
+                (12, 1),
+                (11, 2),
+                (15, 1)
+            ]
+        );
+    }
+}
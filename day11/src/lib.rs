@@ -0,0 +1,106 @@
+use animation::Screen;
+use common::Solution;
+use grid::{Direction, Point, SparseGrid};
+use intcode::{records, Interpreter};
+
+/// Let the robot paint, showing `watch` the panels and where the robot is and which way it's facing
+/// after every move
+fn run(
+    mut interpreter: Interpreter,
+    colors: &mut SparseGrid<bool>,
+    mut watch: impl FnMut(&SparseGrid<bool>, Point, Direction),
+) -> Result<(), records::Error> {
+    let mut position = Point::ORIGIN;
+    let mut facing = Direction::Up;
+
+    loop {
+        interpreter
+            .input
+            .push_back(colors.get(position).copied().unwrap_or(false) as i64);
+
+        interpreter.run();
+
+        if interpreter.done {
+            break;
+        }
+
+        let [color, turn]: [i64; 2] = interpreter.record()?;
+
+        colors.insert(position, color != 0);
+
+        facing = if turn == 0 {
+            facing.turn_left()
+        } else {
+            facing.turn_right()
+        };
+        position += facing;
+
+        watch(colors, position, facing);
+    }
+
+    Ok(())
+}
+
+pub struct Day11 {
+    interpreter: Interpreter,
+}
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut interpreter = Interpreter::parse(input)?;
+        // The robot uses the same memory whatever colour the panels it sees are
+        interpreter
+            .memory
+            .resize(interpreter.required_memory_with(|| 0), 0);
+
+        Ok(Self { interpreter })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        let mut colors = SparseGrid::new();
+        run(self.interpreter.clone(), &mut colors, |_, _, _| {})?;
+        Ok(colors.len().to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        let mut colors = SparseGrid::new();
+        colors.insert(Point::ORIGIN, true);
+
+        run(self.interpreter.clone(), &mut colors, |_, _, _| {})?;
+
+        Ok(colors.render(|color| paint(color.copied())))
+    }
+}
+
+fn paint(color: Option<bool>) -> char {
+    if color == Some(true) {
+        '█'
+    } else {
+        ' '
+    }
+}
+
+impl Day11 {
+    /// Watch the robot paint the registration identifier, returning the identifier
+    pub fn animate(&self, screen: &mut dyn Screen) -> common::Result<String> {
+        let mut colors = SparseGrid::new();
+        colors.insert(Point::ORIGIN, true);
+
+        run(
+            self.interpreter.clone(),
+            &mut colors,
+            |colors, position, facing| {
+                let mut frame = colors
+                    .iter()
+                    .map(|(point, &color)| (point, paint(Some(color))))
+                    .collect::<SparseGrid<char>>();
+                frame.insert(position, facing.arrow());
+                screen.draw(&frame.render(|c| c.copied().unwrap_or(' ')));
+            },
+        )?;
+
+        Ok(colors.render(|color| paint(color.copied())))
+    }
+}
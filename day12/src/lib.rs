@@ -0,0 +1,170 @@
+use common::Solution;
+
+const MOONS: usize = 4;
+const STEPS: usize = 1000;
+
+/// Move the moons along one axis for one time step
+fn step(positions: &mut [i64; MOONS], velocities: &mut [i64; MOONS]) {
+    for i in 0..positions.len() {
+        for j in 0..i {
+            let delta = positions[i].cmp(&positions[j]) as i64;
+            velocities[i] -= delta;
+            velocities[j] += delta;
+        }
+    }
+
+    for i in 0..positions.len() {
+        positions[i] += velocities[i];
+    }
+}
+
+/// How many time steps it takes the moons to get back to where they started along one axis. Each
+/// step can be undone, so the only state which can come before one the moons have been in is the one
+/// which came before it the first time round, and the moons get back to their first state.
+fn period(initial_positions: [i64; MOONS]) -> u64 {
+    let cycle = cycle::brent(
+        (initial_positions, [0; MOONS]),
+        |&(positions, velocities)| {
+            let (mut positions, mut velocities) = (positions, velocities);
+            step(&mut positions, &mut velocities);
+            (positions, velocities)
+        },
+    );
+
+    cycle.length as u64
+}
+
+/// Parse a moon's position, written like `<x=-1, y=0, z=2>`
+fn parse_moon(line: &str) -> Result<[i64; 3], String> {
+    let error = || format!("{:?} isn't a position", line);
+
+    let mut xyz = line
+        .strip_prefix('<')
+        .and_then(|line| line.strip_suffix('>'))
+        .ok_or_else(error)?
+        .split(", ");
+
+    let mut position = [0; 3];
+    for (coordinate, axis) in position.iter_mut().zip(&["x=", "y=", "z="]) {
+        *coordinate = xyz
+            .next()
+            .and_then(|p| p.strip_prefix(axis))
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(error)?;
+    }
+
+    match xyz.next() {
+        Some(_) => Err(error()),
+        None => Ok(position),
+    }
+}
+
+pub struct Day12 {
+    x_axis: [i64; MOONS],
+    y_axis: [i64; MOONS],
+    z_axis: [i64; MOONS],
+}
+
+impl Day12 {
+    /// The total energy in the system after some time steps
+    fn energy_after(&self, steps: usize) -> i64 {
+        let mut axes = [self.x_axis, self.y_axis, self.z_axis];
+        let mut velocities = [[0; MOONS]; 3];
+
+        for _ in 0..steps {
+            for (positions, velocities) in axes.iter_mut().zip(&mut velocities) {
+                step(positions, velocities);
+            }
+        }
+
+        let [x, y, z] = axes;
+        let [vx, vy, vz] = velocities;
+        (0..MOONS)
+            .map(|i| {
+                (x[i].abs() + y[i].abs() + z[i].abs()) * (vx[i].abs() + vy[i].abs() + vz[i].abs())
+            })
+            .sum()
+    }
+}
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut x_axis = [0; MOONS];
+        let mut y_axis = [0; MOONS];
+        let mut z_axis = [0; MOONS];
+
+        let moons = common::parse_lines(input, parse_moon)?;
+        if moons.len() != MOONS {
+            return Err(format!("expected {} moons but got {}", MOONS, moons.len()).into());
+        }
+
+        for (i, [x, y, z]) in moons.into_iter().enumerate() {
+            x_axis[i] = x;
+            y_axis[i] = y;
+            z_axis[i] = z;
+        }
+
+        Ok(Self {
+            x_axis,
+            y_axis,
+            z_axis,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.energy_after(STEPS).to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        // The axes move independently, so the moons are all back where they started once every
+        // axis is
+        Ok([self.x_axis, self.y_axis, self.z_axis]
+            .iter()
+            .map(|&axis| period(axis))
+            .fold(1, cycle::lcm)
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST_EXAMPLE: &str = "\
+<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+    const SECOND_EXAMPLE: &str = "\
+<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>";
+
+    #[test]
+    fn energy_examples() {
+        assert_eq!(Day12::parse(FIRST_EXAMPLE).unwrap().energy_after(10), 179);
+        assert_eq!(
+            Day12::parse(SECOND_EXAMPLE).unwrap().energy_after(100),
+            1940
+        );
+    }
+
+    #[test]
+    fn period_examples() {
+        let day = Day12::parse(FIRST_EXAMPLE).unwrap();
+        assert_eq!(
+            [period(day.x_axis), period(day.y_axis), period(day.z_axis)],
+            [18, 28, 44]
+        );
+
+        assert_eq!(day.part2().unwrap(), "2772");
+        assert_eq!(
+            Day12::parse(SECOND_EXAMPLE).unwrap().part2().unwrap(),
+            "4686774924"
+        );
+    }
+}
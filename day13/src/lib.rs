@@ -0,0 +1,157 @@
+use animation::{Captioned, Screen, Tiles};
+use common::Solution;
+use grid::{Point, SparseGrid};
+use intcode::{records::Record, BlockCache, Interpreter};
+
+const BLOCK: i64 = 2;
+const PADDLE: i64 = 3;
+const BALL: i64 = 4;
+
+/// How each kind of tile is drawn, on the terminal and in images
+const PALETTE: &str = " █#=o";
+pub const COLORS: [[u8; 3]; 5] = [
+    [0x10, 0x10, 0x18],
+    [0x80, 0x80, 0x90],
+    [0xd0, 0x60, 0x30],
+    [0xf0, 0xf0, 0xf0],
+    [0xf0, 0xd0, 0x30],
+];
+
+/// What the arcade cabinet draws
+enum Draw {
+    Tile { x: i64, y: i64, tile: i64 },
+    Score(i64),
+}
+
+impl Record for Draw {
+    const SIZE: usize = 3;
+
+    fn decode(values: &[i64]) -> Option<Self> {
+        Some(match *values {
+            [-1, 0, score] => Draw::Score(score),
+            [x, y, tile] if (0..=4).contains(&tile) => Draw::Tile { x, y, tile },
+            _ => return None,
+        })
+    }
+}
+
+pub struct Day13 {
+    interpreter: Interpreter,
+}
+
+impl Day13 {
+    /// What the game draws before it's played
+    fn screen(&self) -> common::Result<Vec<Draw>> {
+        let mut interpreter = self.interpreter.clone();
+        BlockCache::new().run(&mut interpreter);
+        Ok(interpreter.records().collect::<Result<_, _>>()?)
+    }
+}
+
+/// The x coordinate of every tile of the given kind on the screen
+fn tiles(screen: &[Draw], kind: i64) -> impl Iterator<Item = i64> + '_ {
+    screen.iter().filter_map(move |draw| match *draw {
+        Draw::Tile { x, tile, .. } if tile == kind => Some(x),
+        _ => None,
+    })
+}
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut interpreter = Interpreter::parse(input)?;
+
+        // Playing uses more memory than just drawing the screen, and leaving the joystick alone
+        // plays until the ball's lost
+        let mut free_play = interpreter.clone();
+        free_play.memory[0] = 2;
+        interpreter
+            .memory
+            .resize(free_play.required_memory_with(|| 0), 0);
+
+        Ok(Self { interpreter })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(tiles(&self.screen()?, BLOCK).count().to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(self.play(|_| {})?.to_string())
+    }
+}
+
+impl Day13 {
+    /// Play the game until every block's broken, showing `watch` what's drawn after every move of
+    /// the joystick, and return the final score
+    fn play(&self, mut watch: impl FnMut(&[Draw])) -> common::Result<i64> {
+        let screen = self.screen()?;
+
+        // paddle x coordinate
+        let mut p_x = tiles(&screen, PADDLE).next().ok_or("there's no paddle")?;
+
+        // ball x coordinate
+        let mut b_x = tiles(&screen, BALL).next().ok_or("there's no ball")?;
+
+        let mut interpreter = self.interpreter.clone();
+        interpreter.memory[0] = 2;
+
+        // The game runs a little at a time between moves of the joystick, so its compiled blocks
+        // are kept for the whole game
+        let mut cache = BlockCache::new();
+
+        let mut score = 0;
+
+        while !interpreter.done {
+            interpreter.input.push_back(if b_x < p_x {
+                p_x -= 1;
+                -1
+            } else if b_x != p_x {
+                p_x += 1;
+                1
+            } else {
+                0
+            });
+
+            cache.run(&mut interpreter);
+
+            let draws = interpreter.records().collect::<Result<Vec<Draw>, _>>()?;
+            for draw in &draws {
+                match *draw {
+                    Draw::Score(new_score) => score = new_score,
+                    Draw::Tile { x, tile: BALL, .. } => b_x = x,
+                    Draw::Tile { .. } => {}
+                }
+            }
+
+            watch(&draws);
+        }
+
+        Ok(score)
+    }
+
+    /// Watch the game being played, returning the final score
+    pub fn animate(&self, screen: &mut dyn Screen) -> common::Result<String> {
+        let mut cabinet = SparseGrid::new();
+        let mut score = 0;
+
+        let final_score = self.play(|draws| {
+            for draw in draws {
+                match *draw {
+                    Draw::Score(new_score) => score = new_score,
+                    Draw::Tile { x, y, tile } => {
+                        cabinet.insert(Point::new(x, y), tile);
+                    }
+                }
+            }
+
+            screen.draw(&Captioned::new(
+                Tiles::new(&cabinet, PALETTE),
+                format!("score {}", score),
+            ));
+        })?;
+
+        Ok(final_score.to_string())
+    }
+}
@@ -0,0 +1,260 @@
+use std::{cell::Cell, cmp::Ordering, collections::HashMap};
+
+use common::Solution;
+
+const PART2_ORE: usize = 1_000_000_000_000;
+
+type Ingredient<'a> = (usize, &'a str);
+type Reaction<'a> = (&'a str, (usize, Vec<Ingredient<'a>>));
+
+/// How much of a chemical a reaction makes and what it needs, by the chemical it makes
+type Recipes = HashMap<String, (usize, Vec<(usize, String)>)>;
+
+fn parse_ingredient(product_per_recipe: &str) -> Result<Ingredient<'_>, String> {
+    product_per_recipe
+        .split_once(' ')
+        .and_then(|(n, product)| Some((n.parse().ok()?, product)))
+        .ok_or_else(|| format!("{:?} isn't an amount of a chemical", product_per_recipe))
+}
+
+fn parse_reaction(reaction: &str) -> Result<Reaction<'_>, String> {
+    let (lhs, rhs) = reaction
+        .split_once(" => ")
+        .ok_or_else(|| format!("{:?} isn't a reaction", reaction))?;
+
+    let lhs = lhs
+        .split(", ")
+        .map(parse_ingredient)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (n, rhs) = parse_ingredient(rhs)?;
+
+    Ok((rhs, (n, lhs)))
+}
+
+struct Solver<'a> {
+    recipes: &'a Recipes,
+    had: HashMap<&'a str, Cell<usize>>,
+    ore_cost: Cell<usize>,
+}
+
+impl<'a> Solver<'a> {
+    fn new(recipes: &'a Recipes) -> Self {
+        Self {
+            had: recipes
+                .keys()
+                .map(|key| (key.as_str(), Cell::default()))
+                .collect(),
+            ore_cost: Cell::default(),
+            recipes,
+        }
+    }
+
+    fn make(&self, product: &str, needed: usize) {
+        // If we're making ORE, we just need to keep track of how much we need
+        if product == "ORE" {
+            self.ore_cost.set(self.ore_cost.get() + needed);
+            return;
+        }
+
+        // We don't need to make what we already have
+        let product_had = self.had.get(product).unwrap();
+
+        // If needed - product_had underflows we already have enough product
+        if let Some(needed) = needed.checked_sub(product_had.get()) {
+            // Figure out how many the recipe makes
+            let (product_per_recipe, ingredients) = self.recipes.get(product).unwrap();
+
+            // How many times do we need to run the recipe?
+            let recipes_made = needed.div_ceil(*product_per_recipe);
+
+            for (needed, ingredient) in ingredients {
+                let (needed, ingredient) = (*needed, ingredient.as_str());
+
+                // Make it ...
+                self.make(ingredient, needed * recipes_made);
+
+                // ... and use it
+                if ingredient != "ORE" {
+                    let ingredient_had = self.had.get(ingredient).unwrap();
+                    ingredient_had.set(ingredient_had.get() - needed * recipes_made);
+                }
+            }
+
+            // And produce the result
+            product_had.set(product_had.get() + recipes_made * product_per_recipe)
+        }
+    }
+}
+
+pub struct Day14 {
+    recipes: Recipes,
+}
+
+impl Day14 {
+    /// How much ore it takes to make the given amount of fuel
+    fn ore_cost(&self, fuel: usize) -> usize {
+        let solver = Solver::new(&self.recipes);
+        solver.make("FUEL", fuel);
+        solver.ore_cost.get()
+    }
+}
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let recipes = common::parse_lines(input, parse_reaction)?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        // Everything has to be made out of ore in the end
+        if !recipes.contains_key("FUEL") {
+            return Err("there's no way to make fuel".into());
+        }
+        for (_, ingredients) in recipes.values() {
+            for &(_, ingredient) in ingredients {
+                if ingredient != "ORE" && !recipes.contains_key(ingredient) {
+                    return Err(format!("there's no way to make {}", ingredient).into());
+                }
+            }
+        }
+
+        let recipes = recipes
+            .into_iter()
+            .map(|(product, (n, ingredients))| {
+                let ingredients = ingredients
+                    .into_iter()
+                    .map(|(needed, ingredient)| (needed, ingredient.to_owned()))
+                    .collect();
+                (product.to_owned(), (n, ingredients))
+            })
+            .collect();
+
+        Ok(Self { recipes })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.ore_cost(1).to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        // Part one represents the maximum cost for 1 FUEL because we started from scratch. Due to
+        // this, we can calculate a lower bound on the solution by considering how much we could
+        // make assuming we started from scratch every time
+        let mut l = PART2_ORE / self.ore_cost(1);
+        let mut r = PART2_ORE;
+
+        while l <= r {
+            let fuel = (l + r) / 2;
+
+            match self.ore_cost(fuel).cmp(&PART2_ORE) {
+                Ordering::Less => l = fuel + 1,
+                Ordering::Greater => r = fuel - 1,
+                Ordering::Equal => break,
+            }
+        }
+
+        Ok(((l + r) / 2).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(reactions: &str) -> (String, String) {
+        let day = Day14::parse(reactions).unwrap();
+        (day.part1().unwrap(), day.part2().unwrap())
+    }
+
+    #[test]
+    fn part1_examples() {
+        let day = Day14::parse(
+            "\
+10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL",
+        )
+        .unwrap();
+        assert_eq!(day.part1().unwrap(), "31");
+
+        let day = Day14::parse(
+            "\
+9 ORE => 2 A
+8 ORE => 3 B
+7 ORE => 5 C
+3 A, 4 B => 1 AB
+5 B, 7 C => 1 BC
+4 C, 1 A => 1 CA
+2 AB, 3 BC, 4 CA => 1 FUEL",
+        )
+        .unwrap();
+        assert_eq!(day.part1().unwrap(), "165");
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(
+            solve(
+                "\
+157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT"
+            ),
+            ("13312".into(), "82892753".into())
+        );
+
+        assert_eq!(
+            solve(
+                "\
+2 VPVL, 7 FWMGM, 2 CXFTF, 11 MNCFX => 1 STKFG
+17 NVRVD, 3 JNWZP => 8 VPVL
+53 STKFG, 6 MNCFX, 46 VJHF, 81 HVMC, 68 CXFTF, 25 GNMV => 1 FUEL
+22 VJHF, 37 MNCFX => 5 FWMGM
+139 ORE => 4 NVRVD
+144 ORE => 7 JNWZP
+5 MNCFX, 7 RFSQX, 2 FWMGM, 2 VPVL, 19 CXFTF => 3 HVMC
+5 VJHF, 7 MNCFX, 9 VPVL, 37 CXFTF => 6 GNMV
+145 ORE => 6 MNCFX
+1 NVRVD => 8 CXFTF
+1 VJHF, 6 MNCFX => 4 RFSQX
+176 ORE => 6 VJHF"
+            ),
+            ("180697".into(), "5586022".into())
+        );
+
+        assert_eq!(
+            solve(
+                "\
+171 ORE => 8 CNZTR
+7 ZLQW, 3 BMBT, 9 XCVML, 26 XMNCP, 1 WPTQ, 2 MZWV, 1 RJRHP => 4 PLWSL
+114 ORE => 4 BHXH
+14 VRPVC => 6 BMBT
+6 BHXH, 18 KTJDG, 12 WPTQ, 7 PLWSL, 31 FHTLT, 37 ZDVW => 1 FUEL
+6 WPTQ, 2 BMBT, 8 ZLQW, 18 KTJDG, 1 XMNCP, 6 MZWV, 1 RJRHP => 6 FHTLT
+15 XDBXC, 2 LTCX, 1 VRPVC => 6 ZLQW
+13 WPTQ, 10 LTCX, 3 RJRHP, 14 XMNCP, 2 MZWV, 1 ZLQW => 1 ZDVW
+5 BMBT => 4 WPTQ
+189 ORE => 9 KTJDG
+1 MZWV, 17 XDBXC, 3 XCVML => 2 XMNCP
+12 VRPVC, 27 CNZTR => 2 XDBXC
+15 KTJDG, 12 BHXH => 5 XCVML
+3 BHXH, 2 VRPVC => 7 MZWV
+121 ORE => 7 VRPVC
+7 XCVML => 6 RJRHP
+5 BHXH, 4 VRPVC => 5 LTCX"
+            ),
+            ("2210736".into(), "460664".into())
+        );
+    }
+}
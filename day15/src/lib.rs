@@ -0,0 +1,193 @@
+use std::iter::once;
+
+use animation::{Screen, Tiles};
+use common::Solution;
+use grid::{Direction, Point, SparseGrid};
+use intcode::Interpreter;
+
+/// The directions the droid can be told to move in, in the order of the commands for them
+const COMMANDS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+fn command(direction: Direction) -> i64 {
+    COMMANDS.iter().position(|&d| d == direction).unwrap() as i64 + 1
+}
+
+/// What the droid reports after being told to move, which is what's at the point it tried to move
+/// to
+const WALL: i64 = 0;
+const OPEN: i64 = 1;
+const OXYGEN: i64 = 2;
+
+/// Everything the droid has found so far, and the droid itself, showing `watch` the map and where
+/// the droid is after every move it's told to make
+struct Explorer<W> {
+    interpreter: Interpreter,
+    map: SparseGrid<i64>,
+    watch: W,
+}
+
+impl<W: FnMut(&SparseGrid<i64>, Point)> Explorer<W> {
+    /// Move the droid to every point reachable from its position which hasn't been found yet,
+    /// bringing it back there afterwards
+    fn explore(&mut self, position: Point) {
+        // For each candidate unexplored spot
+        for &direction in &COMMANDS {
+            // If we have not explored it yet
+            let candidate = position + direction;
+            if self.map.contains(candidate) {
+                continue;
+            }
+
+            // Tell the robot to visit it
+            self.interpreter.input.push_front(command(direction));
+            self.interpreter.run();
+
+            // Get the robot's information about it
+            let space = self.interpreter.output.pop_front().unwrap();
+            self.map.insert(candidate, space);
+
+            // If we couldn't move there, move on
+            if space == WALL {
+                (self.watch)(&self.map, position);
+                continue;
+            }
+            (self.watch)(&self.map, candidate);
+
+            // Otherwise keep on exploring from there
+            self.explore(candidate);
+
+            // After we've explored that point fully, tell the bot to move back to the original
+            // position and continue with the next candidate
+            self.interpreter
+                .input
+                .push_front(command(direction.opposite()));
+            self.interpreter.run();
+            assert_ne!(self.interpreter.output.pop_front(), Some(WALL));
+            (self.watch)(&self.map, position);
+        }
+    }
+}
+
+pub struct Day15 {
+    interpreter: Interpreter,
+}
+
+impl Day15 {
+    /// Send the droid everywhere it can go, returning the map of the area and where the oxygen
+    /// system is
+    fn explore(
+        &self,
+        watch: impl FnMut(&SparseGrid<i64>, Point),
+    ) -> common::Result<(SparseGrid<i64>, Point)> {
+        let mut explorer = Explorer {
+            interpreter: self.interpreter.clone(),
+            map: SparseGrid::new(),
+            watch,
+        };
+        explorer.map.insert(Point::ORIGIN, OPEN);
+        explorer.explore(Point::ORIGIN);
+
+        let oxygen = explorer
+            .map
+            .iter()
+            .find(|&(_, &space)| space == OXYGEN)
+            .map(|(position, _)| position)
+            .ok_or("the droid never found the oxygen system")?;
+        Ok((explorer.map, oxygen))
+    }
+}
+
+/// The points next to a point which the droid can move to
+fn open(map: &SparseGrid<i64>, position: Point) -> impl Iterator<Item = Point> + '_ {
+    position
+        .neighbors()
+        .filter(move |&neighbor| map.get(neighbor).is_some_and(|&space| space != WALL))
+}
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            interpreter: Interpreter::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        // The solution to part1 is the distance from the start to the oxygen
+        let (map, oxygen) = self.explore(|_, _| {})?;
+        let path = search::bfs(
+            once(Point::ORIGIN),
+            |&position| open(&map, position),
+            |&position| position == oxygen,
+        )
+        .unwrap();
+        Ok(path.cost.to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        // The oxygen takes a minute to spread a step, so it fills the area once it's reached the
+        // farthest point from the oxygen system
+        let (map, oxygen) = self.explore(|_, _| {})?;
+        let filled = search::bfs_all(once(oxygen), |&position| open(&map, position));
+        Ok(filled
+            .iter()
+            .map(|(_, minutes)| minutes)
+            .max()
+            .unwrap()
+            .to_string())
+    }
+}
+
+/// Where the droid is and what it hasn't found yet, which are only ever drawn and never found on
+/// the map
+const DROID: i64 = 3;
+const UNEXPLORED: i64 = 4;
+
+/// How each kind of tile is drawn, on the terminal and in images
+const PALETTE: &str = "█.OD ";
+pub const COLORS: [[u8; 3]; 5] = [
+    [0x30, 0x30, 0x38],
+    [0xe0, 0xe0, 0xd8],
+    [0x40, 0x90, 0xf0],
+    [0xe0, 0x30, 0x30],
+    [0x00, 0x00, 0x00],
+];
+
+impl Day15 {
+    /// Watch the droid explore the area, and then the oxygen spread through it, returning how long
+    /// the oxygen takes to fill it
+    pub fn animate(&self, screen: &mut dyn Screen) -> common::Result<String> {
+        // Every frame is drawn as big as the whole area, so it doesn't grow as the droid finds more
+        // of it, which means finding all of it before watching the droid do so
+        let (min, max) = self.explore(|_, _| {})?.0.bounds().unwrap();
+        let (mut map, oxygen) = self.explore(|map, droid| {
+            let mut frame = map.clone();
+            frame.entry(min).or_insert(UNEXPLORED);
+            frame.entry(max).or_insert(UNEXPLORED);
+            frame.insert(droid, DROID);
+            screen.draw(&Tiles::new(&frame, PALETTE));
+        })?;
+
+        let filled = search::bfs_all(once(oxygen), |&position| open(&map, position));
+        let mut spreading = filled.iter().peekable();
+        let mut minutes = 0;
+        while let Some((&position, minute)) = spreading.next() {
+            map.insert(position, OXYGEN);
+            minutes = minute;
+
+            // The search reaches every point a minute away before any further away, so a minute's
+            // over once the next point is further
+            if spreading.peek().is_none_or(|&(_, next)| next != minute) {
+                screen.draw(&Tiles::new(&map, PALETTE));
+            }
+        }
+
+        Ok(minutes.to_string())
+    }
+}
@@ -0,0 +1,227 @@
+use std::cmp::min;
+
+use common::Solution;
+
+const REPETITION: usize = 10_000;
+
+const ITERATIONS: u32 = 100;
+
+fn digit(signal: &[i32], idx: usize) -> i32 {
+    let mut total: i32 = 0;
+
+    for i in (idx..signal.len()).step_by(4 * (idx + 1)) {
+        total += signal[i..min(signal.len(), i + (idx + 1))]
+            .iter()
+            .sum::<i32>();
+    }
+
+    for i in (3 * (idx + 1) - 1..signal.len()).step_by(4 * (idx + 1)) {
+        total -= signal[i..min(signal.len(), i + idx + 1)]
+            .iter()
+            .sum::<i32>();
+    }
+
+    total.abs() % 10
+}
+
+fn fft_part1(signal: &[i32]) -> Vec<i32> {
+    let mut next = vec![0; signal.len()];
+    // We utilize `iter_mut().enumerate()` to avoid bounds checking
+    for (i, elem) in next.iter_mut().enumerate() {
+        *elem = digit(signal, i);
+    }
+    next
+}
+
+// Calculate mCn (mod 2) using some bitwise trickery
+fn binom_mod2(m: u32, n: u32) -> u32 {
+    ((!m & n) == 0) as u32
+}
+
+// Calculate mCn (mod 5) using Lucas's theorem
+fn binom_mod5(mut m: u32, mut n: u32) -> u32 {
+    // Precalculated array of all possible binomials for m and n less than 5
+    const LOOKUP_TABLE: [u32; 5 * 5] = [
+        1, 0, 0, 0, 0, 1, 1, 0, 0, 0, 1, 2, 1, 0, 0, 1, 3, 3, 1, 0, 1, 4, 1, 4, 1,
+    ];
+
+    let mut res = 1;
+    while m != 0 && n != 0 && res != 0 {
+        res *= LOOKUP_TABLE[(5 * (m % 5) + (n % 5)) as usize];
+        res %= 5;
+        m /= 5;
+        n /= 5;
+    }
+    res
+}
+
+// Calculate mCn (mod 10) using the Chinese Remainder Theorem
+fn binom_mod10(m: u32, n: u32) -> u32 {
+    if m < n {
+        return 0;
+    }
+
+    let m2 = binom_mod2(m, n);
+    let m5 = binom_mod5(m, n);
+
+    // To utilize the CRT, we must find an integer satisfying two linear congruences
+    // 1) x = m2 (mod 2)
+    // 2) x = m5 (mod 5)
+    // Since the first equation is modulo two, there are only two possibilities for m2, zero and
+    // one, so we can already restrict the search space in half by considering evens/odds. Then, we
+    // order the considered numbers by their remainder modulo 5 and index into it like a lookup table.
+    (if m2 == 0 {
+        // 0 % 5 = 0
+        // 2 % 5 = 2
+        // 4 % 5 = 4
+        // 6 % 5 = 1
+        // 8 % 5 = 3
+        [0, 6, 2, 8, 4]
+    } else {
+        // 1 % 5 = 1
+        // 3 % 5 = 3
+        // 5 % 5 = 0
+        // 7 % 5 = 2
+        // 9 % 5 = 4
+        [5, 1, 7, 3, 9]
+    })[m5 as usize]
+}
+
+pub struct Day16 {
+    signal: Vec<i32>,
+}
+
+impl Day16 {
+    /// Run the FFT algorithm on the signal for the given number of phases
+    fn after_phases(&self, phases: u32) -> Vec<i32> {
+        let mut signal = self.signal.clone();
+        (0..phases).for_each(|_| signal = fft_part1(&signal));
+        signal
+    }
+}
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let signal = input
+            .trim()
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|digit| digit as i32)
+                    .ok_or_else(|| format!("{:?} isn't a digit", c))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if signal.len() < 8 {
+            return Err("the signal is shorter than the message".into());
+        }
+
+        Ok(Self { signal })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.after_phases(ITERATIONS)[..8]
+            .iter()
+            .map(|c| c.to_string())
+            .collect())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        let len = self.signal.len();
+
+        // The message's offset is the signal's first seven digits
+        let offset = self.signal[..7]
+            .iter()
+            .fold(0, |offset, &digit| 10 * offset + digit as usize);
+
+        // Part 2's trick only works for messages in the second half of the signal
+        if offset < len * REPETITION / 2 || offset + 8 > len * REPETITION {
+            return Err(
+                format!("the message's offset, {}, is too small or too big", offset).into(),
+            );
+        }
+        let unsigned_signal = self
+            .signal
+            .iter()
+            .map(|&digit| digit as u32)
+            .collect::<Vec<_>>();
+
+        // Solve part 2 in a slightly smarter way by noticing that the FFT algorithm corresponds to
+        // summing in reverse if the offset is large enough. This is then equivalent to multiplying
+        // a vector by a matrix raised to the 100th power, and this particular matrix is an
+        // unitriangular matrix, which are defined to have only ones above the main diagonal. A
+        // particular property of this kind of matrix is that their exponetiation is given by the
+        // Nth diagonal on Pascal's triangle, which can be calculated with binomial coefficients.
+        // Shifting the row by one for every digit we get the answer to part 2 in as little
+        // computation as possible
+        let coeffs = (0..(len * REPETITION - offset) as u32)
+            .map(|i| binom_mod10(ITERATIONS - 1 + i, i))
+            .collect::<Vec<_>>();
+
+        let blocks = offset / len;
+
+        let mut message = String::new();
+
+        // For every digit
+        for k in 0..8 {
+            // Start iterating through the coefficients, this will automatically advance between
+            // the different blocks we use it and keep track or where we are
+            let mut coeffs = coeffs.iter().copied();
+
+            let mut d = 0;
+
+            // Consider the part of the input that is not repeated fully
+            d += unsigned_signal
+                .iter()
+                .skip(offset - blocks * len + k)
+                .zip(coeffs.by_ref())
+                .map(|(a, b)| a * b)
+                .sum::<u32>();
+
+            // Then consider rest of the input which is repeated in toto
+            for _ in blocks + 1..REPETITION {
+                d += unsigned_signal
+                    .iter()
+                    .zip(coeffs.by_ref())
+                    .map(|(a, b)| a * b)
+                    .sum::<u32>();
+            }
+
+            // Limit the digit to be, well, a digit and add it to the message
+            message.push_str(&(d % 10).to_string());
+        }
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part2(signal: &str) -> String {
+        Day16::parse(signal).unwrap().part2().unwrap()
+    }
+
+    #[test]
+    fn phases_example() {
+        let day = Day16::parse("12345678").unwrap();
+        let phases = (1..=4)
+            .map(|phases| {
+                day.after_phases(phases)
+                    .iter()
+                    .map(|digit| digit.to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(phases, ["48226158", "34040438", "03415518", "01029498"]);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(part2("03036732577212944063491565474664"), "84462026");
+        assert_eq!(part2("02935109699940807407585447034323"), "78725270");
+        assert_eq!(part2("03081770884921959731165446850517"), "53553731");
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::HashSet;
+
+use animation::Screen;
+use common::Solution;
+use grid::{Direction, Grid, Point};
+use intcode::{ascii, BlockCache, Interpreter};
+
+const MIN_ROUTINE_LEN: usize = 6;
+
+/// Where the robot is on the scaffolding and which way it's facing
+type Robot = (Point, Direction);
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum Move {
+    RotateRight,
+    RotateLeft,
+    Forward(usize),
+
+    RoutineA,
+    RoutineB,
+    RoutineC,
+}
+
+impl Move {
+    fn is_routine(&self) -> bool {
+        *self == Move::RoutineA || *self == Move::RoutineB || *self == Move::RoutineC
+    }
+}
+
+fn add_move(path: &mut Vec<Move>, steps: usize) {
+    if steps == 0 {
+        return;
+    }
+
+    if let Some(Move::Forward(n)) = path.last_mut() {
+        *n += steps;
+    } else {
+        path.push(Move::Forward(steps));
+    }
+}
+
+fn apply_routine(path: &[Move], routine: &[Move], routine_move: Move) -> Vec<Move> {
+    debug_assert!(!routine.is_empty());
+
+    let mut rest = Vec::with_capacity(path.len());
+
+    let mut k = 0;
+    while k < path.len() {
+        if path[k..].starts_with(routine) {
+            rest.push(routine_move);
+            k += routine.len();
+        } else {
+            rest.push(path[k]);
+            k += 1;
+        }
+    }
+
+    rest
+}
+
+fn path_input(path: &[Move]) -> Vec<i64> {
+    let mut result = Vec::with_capacity(path_len(path) + 1);
+
+    for (i, step) in path.iter().enumerate() {
+        match step {
+            Move::RotateLeft => result.push(b'L' as i64),
+            Move::RotateRight => result.push(b'R' as i64),
+            Move::Forward(n) => result.extend(n.to_string().bytes().map(|c| c as i64)),
+            Move::RoutineA => result.push(b'A' as i64),
+            Move::RoutineB => result.push(b'B' as i64),
+            Move::RoutineC => result.push(b'C' as i64),
+        }
+
+        result.push(if i == path.len() - 1 {
+            b'\n' as i64
+        } else {
+            b',' as i64
+        });
+    }
+
+    result
+}
+
+fn digits(n: usize) -> usize {
+    debug_assert!(n < 100);
+    if n < 10 {
+        1
+    } else {
+        2
+    }
+}
+
+fn path_len(path: &[Move]) -> usize {
+    path.iter()
+        .map(|&step| {
+            if let Move::Forward(n) = step {
+                digits(n)
+            } else {
+                1
+            }
+        })
+        .sum::<usize>()
+        + (path.len() - 1)
+}
+
+/// Read where the scaffolds and the robot are from the camera's view of them
+fn read_map(map: &str) -> common::Result<(HashSet<Point>, Robot)> {
+    let map = Grid::parse(map, |c| match c {
+        '#' | '.' | 'X' => Ok(c),
+        _ if Direction::parse(c).is_some() => Ok(c),
+        _ => Err(format!("{:?} isn't part of the camera's view", c)),
+    })?;
+
+    let mut scaffolds = HashSet::new();
+    let mut robot = None;
+
+    for (position, &c) in map.iter() {
+        let position = Point::new(position.x as i64, position.y as i64);
+        match c {
+            '#' => {
+                scaffolds.insert(position);
+            }
+            'X' => return Err("the robot is tumbling through space".into()),
+            _ => {
+                robot = Direction::parse(c)
+                    .map(|facing| (position, facing))
+                    .or(robot)
+            }
+        }
+    }
+
+    Ok((
+        scaffolds,
+        robot.ok_or("the robot isn't on the scaffolding")?,
+    ))
+}
+
+/// Sum the alignment parameters of the scaffold intersections
+fn alignment(scaffolds: &HashSet<Point>) -> i64 {
+    scaffolds
+        .iter()
+        .filter(|position| {
+            position
+                .neighbors()
+                .all(|neighbor| scaffolds.contains(&neighbor))
+        })
+        .map(|position| position.x * position.y)
+        .sum()
+}
+
+/// Find the moves which take the robot over every scaffold
+fn find_path(mut scaffolds: HashSet<Point>, (mut position, mut facing): Robot) -> Vec<Move> {
+    // how many steps we've taken since the last rotation
+    let mut steps = 0usize;
+
+    // how many rotations we've taken since the last step
+    let mut rotations = 0u8;
+
+    let mut path: Vec<Move> = Vec::new();
+
+    while !scaffolds.is_empty() {
+        let tentative_position = position + facing;
+
+        if scaffolds.remove(&tentative_position) || rotations == 4 {
+            if rotations == 1 {
+                path.push(Move::RotateRight);
+            } else if rotations == 3 {
+                path.push(Move::RotateLeft);
+            }
+
+            position = tentative_position;
+            steps += 1;
+            rotations = 0;
+        } else {
+            facing = facing.turn_right();
+
+            add_move(&mut path, steps);
+
+            steps = 0;
+            rotations += 1;
+        }
+    }
+    add_move(&mut path, steps);
+
+    path
+}
+
+pub struct Day17 {
+    interpreter: Interpreter,
+}
+
+impl Day17 {
+    /// Wake the robot up and look at the scaffolding, returning the robot waiting for its routines,
+    /// the scaffolds and where the robot is on them
+    fn wake(&self) -> common::Result<(Interpreter, HashSet<Point>, Robot)> {
+        let mut interpreter = self.interpreter.clone();
+        interpreter.memory[0] = 2;
+
+        interpreter.run();
+
+        // The map ends with an empty line, after which the program prompts for the main routine
+        let map = ascii::read_until(&mut interpreter.output, "\n\n")?;
+        let (scaffolds, robot) = read_map(&map)?;
+        interpreter.output.clear();
+
+        Ok((interpreter, scaffolds, robot))
+    }
+}
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut interpreter = Interpreter::parse(input)?;
+        interpreter.memory.extend_from_slice(&[0; 2000]);
+        Ok(Self { interpreter })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        let (_, scaffolds, _) = self.wake()?;
+
+        Ok(alignment(&scaffolds).to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        let (mut interpreter, scaffolds, robot) = self.wake()?;
+        send_routines(&mut interpreter, &find_path(scaffolds, robot), false)?;
+
+        interpreter.run();
+
+        Ok(ascii::answer(&interpreter.output)?.to_string())
+    }
+}
+
+/// Split the path into the main routine and the three movement routines it calls, and send them to
+/// the robot, asking for the continuous video feed or not
+fn send_routines(interpreter: &mut Interpreter, path: &[Move], video: bool) -> common::Result<()> {
+    // Restriction: The main sequence must begin with A,B
+
+    for a_end in MIN_ROUTINE_LEN..path.len() {
+        let a = &path[..a_end];
+        let path = apply_routine(path, a, Move::RoutineA);
+
+        for b_end in MIN_ROUTINE_LEN..path.len() {
+            let b = &path[1..b_end];
+            let path = apply_routine(&path, b, Move::RoutineB);
+
+            // C must be everything that remains between routines
+            let mut it = path.iter();
+            let c_start = it.by_ref().position(|m| !m.is_routine()).unwrap();
+            let c_end = (c_start + 1) + it.position(|m| m.is_routine()).unwrap_or(0);
+
+            let c = &path[c_start..c_end];
+
+            let path = apply_routine(&path, c, Move::RoutineC);
+
+            if path_len(&path) < 20 {
+                interpreter.input.extend(path_input(&path));
+                interpreter.input.extend(path_input(a));
+                interpreter.input.extend(path_input(b));
+                interpreter.input.extend(path_input(c));
+                ascii::send_line(interpreter, if video { "y" } else { "n" })?;
+                return Ok(());
+            }
+        }
+    }
+
+    Err("the path can't be split into movement routines".into())
+}
+
+impl Day17 {
+    /// Watch the robot walk the scaffolding through its continuous video feed, returning how much
+    /// dust it collects
+    pub fn animate(&self, screen: &mut dyn Screen) -> common::Result<String> {
+        let (mut interpreter, scaffolds, robot) = self.wake()?;
+        send_routines(&mut interpreter, &find_path(scaffolds, robot), true)?;
+
+        // Drawing the feed runs for far longer than `run` can recurse in debug builds
+        BlockCache::new().run(&mut interpreter);
+        ascii::read_until(&mut interpreter.output, "Continuous video feed?\n\n")?;
+
+        // The feed shows the camera's view after each of the robot's moves, each view ending with
+        // an empty line, and then the dust collected
+        while interpreter.output.len() > 1 {
+            let view = ascii::read_until(&mut interpreter.output, "\n\n")?;
+            screen.draw(&view);
+        }
+
+        Ok(ascii::answer(&interpreter.output)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alignment_example() {
+        let (scaffolds, _) = read_map(
+            "\
+..#..........
+..#..........
+#######...###
+#.#...#...#.#
+#############
+..#...#...#..
+..#####...^..",
+        )
+        .unwrap();
+        assert_eq!(alignment(&scaffolds), 76);
+    }
+
+    #[test]
+    fn path_example() {
+        let (scaffolds, robot) = read_map(
+            "\
+#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......",
+        )
+        .unwrap();
+        let path = path_input(&find_path(scaffolds, robot))
+            .into_iter()
+            .map(|c| c as u8 as char)
+            .collect::<String>();
+        assert_eq!(
+            path,
+            "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2\n"
+        );
+    }
+}
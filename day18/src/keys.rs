@@ -50,8 +50,4 @@ impl Keys {
     pub fn has_all(self, required: Self) -> bool {
         self.0 & required.0 == required.0
     }
-
-    pub fn count(self) -> u32 {
-        self.0.count_ones()
-    }
 }
@@ -0,0 +1,384 @@
+use std::{
+    collections::{HashMap, HashSet},
+    iter::once,
+};
+
+use common::Solution;
+use grid::Grid;
+
+const EMPTY: u8 = b'.';
+const ENTRANCE: u8 = b'@';
+const WALL: u8 = b'#';
+
+type Point = grid::Point<u16>;
+
+/// A Graph is a mapping from a Point to the states you can reach from it
+type Graph = HashMap<Point, Vec<State>>;
+
+mod keys;
+use keys::{Keys, KEYS};
+
+/// A path to a key, which gains the keys along it and needs the keys to the doors along it
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+struct State {
+    position: Point,
+    distance: u16,
+    keys_gained: Keys,
+    keys_needed: Keys,
+}
+
+/// Calculate the fewest steps the robots have to take between them to pick up all the keys, moving
+/// one robot from key to key at a time
+fn solve<const ROBOTS: usize>(
+    robots: [Point; ROBOTS],
+    initial_keys: Keys,
+    graph: &Graph,
+) -> Option<u16> {
+    // Search through where the robots are and which keys they've collected between them, going from
+    // one arrangement to another by sending a robot off to one of the keys it can reach
+    let collected = search::dijkstra(
+        once((robots, initial_keys)),
+        |&(robots, keys): &([Point; ROBOTS], Keys)| {
+            (0..ROBOTS).flat_map(move |idx| {
+                graph[&robots[idx]]
+                    .iter()
+                    // Consider only keys which we can reach and which give us new keys
+                    .filter(move |next_state| {
+                        keys.has_all(next_state.keys_needed)
+                            && !keys.has_all(next_state.keys_gained)
+                    })
+                    .map(move |next_state| {
+                        let mut next_robots = robots;
+                        next_robots[idx] = next_state.position;
+                        (
+                            (next_robots, keys.add_all(next_state.keys_gained)),
+                            next_state.distance,
+                        )
+                    })
+            })
+        },
+        |&(_, keys)| keys == Keys::all(),
+    )?;
+
+    Some(collected.cost)
+}
+
+/// Run a breadth-first search from a point, returning a state for each key reachable from it which
+/// gains the keys along the way there and needs the keys for the doors along the way there
+fn paths_from(vault: &Grid<u8>, start: Point) -> Vec<State> {
+    let reachable = search::bfs_all(once(start), |&position| {
+        vault
+            .neighbors(position)
+            .filter(|&neighbor| vault[neighbor] != WALL)
+    });
+
+    reachable
+        .iter()
+        .filter(|&(&position, _)| vault[position].is_ascii_lowercase() && position != start)
+        .map(|(&position, distance)| {
+            let path = reachable.path(&position).unwrap();
+
+            // Every key along the way is picked up, and every door along the way has to be opened
+            // with its corresponding key
+            let (keys_gained, keys_needed) = path.nodes[1..].iter().fold(
+                (Keys::none(), Keys::none()),
+                |(gained, needed), &point| match vault[point] {
+                    key if key.is_ascii_lowercase() => (gained.add(key), needed),
+                    door if door.is_ascii_uppercase() => {
+                        (gained, needed.add(door.to_ascii_lowercase()))
+                    }
+                    _ => (gained, needed),
+                },
+            );
+
+            State {
+                position,
+                distance: distance as u16,
+                keys_gained,
+                keys_needed,
+            }
+        })
+        .collect()
+}
+
+/// Create the Graph of the keys reachable from an entrance
+fn build_graph(vault: &Grid<u8>, entrance: Point) -> Graph {
+    // Pre-allocate the graph with enough capacity for all the keys and the entrance.
+    let mut graph: Graph = HashMap::with_capacity(KEYS as usize + 1);
+
+    let from_entrance = paths_from(vault, entrance);
+    for state in &from_entrance {
+        graph.insert(state.position, paths_from(vault, state.position));
+    }
+    graph.insert(entrance, from_entrance);
+
+    // Avoid considering states which are redundant:
+    // If from node A when can reach nodes B and C and reaching node C requires going through node
+    // B but not through any doors (that you haven't already gone through while reaching B), then
+    // there's no reason to consider the state (A -> B) because key C must be picked up anyways
+    for edges in graph.values_mut() {
+        // Group states by keys required to reach them
+        let mut groups: HashMap<Keys, Vec<State>> = Default::default();
+        edges
+            .iter()
+            .for_each(|&state| groups.entry(state.keys_needed).or_default().push(state));
+
+        let mut new_edges: HashSet<State> = HashSet::new();
+        for (_, mut group) in groups {
+            // For all nodes in the current group
+            for _ in 0..group.len() {
+                let node1 = group.remove(0);
+                new_edges.insert(node1);
+
+                // If any of the nodes not considered yet render this one redundant (as explained
+                // above), remove it from the new edges
+                for &node2 in &group {
+                    if node2.keys_gained.has_all(node1.keys_gained) {
+                        new_edges.remove(&node1);
+                        break;
+                    }
+                }
+            }
+        }
+
+        *edges = new_edges.into_iter().collect();
+    }
+
+    graph
+}
+
+/// Calculate the fewest steps to collect every key in the vault with robots starting from the
+/// given entrances
+fn collect_keys<const ROBOTS: usize>(
+    vault: &Grid<u8>,
+    entrances: [Point; ROBOTS],
+) -> common::Result<String> {
+    let mut graph = Graph::new();
+    for &entrance in &entrances {
+        graph.extend(build_graph(vault, entrance));
+    }
+
+    // Pretend to already have the keys that aren't in the vault
+    let initial_keys = vault
+        .iter()
+        .map(|(_, c)| c)
+        .filter(|c| c.is_ascii_lowercase())
+        .fold(Keys::all(), |keys, &key| keys.remove(key));
+
+    match solve(entrances, initial_keys, &graph) {
+        Some(steps) => Ok(steps.to_string()),
+        None => Err("the keys can't all be collected".into()),
+    }
+}
+
+pub struct Day18 {
+    /// The vault with its entrances as empty space
+    vault: Grid<u8>,
+    entrances: Vec<Point>,
+}
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut vault = Grid::parse(input, |c| match c {
+            '#' | '.' | '@' | 'a'..='z' | 'A'..='Z' => Ok(c as u8),
+            _ => Err(format!("{:?} isn't part of a vault", c)),
+        })?;
+        if vault.width() > u16::MAX as usize || vault.height() > u16::MAX as usize {
+            return Err("the vault is too big".into());
+        }
+
+        let entrances = vault
+            .positions(ENTRANCE)
+            .map(|entrance| Point::new(entrance.x as u16, entrance.y as u16))
+            .collect::<Vec<_>>();
+        for &entrance in &entrances {
+            vault[entrance] = EMPTY;
+        }
+
+        if entrances.is_empty() {
+            return Err("the vault has no entrance".into());
+        }
+
+        Ok(Self { vault, entrances })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        match *self.entrances {
+            [entrance] => collect_keys(&self.vault, [entrance]),
+            _ => Err("the vault needs exactly one entrance".into()),
+        }
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        let mut vault = self.vault.clone();
+
+        let entrances = match *self.entrances {
+            // Split the vault into four by walling off the entrance and the spaces next to it, and
+            // sending a robot in from each of its corners
+            [entrance] => {
+                let around =
+                    |dx: u16, dy: u16| Point::new(entrance.x + dx - 1, entrance.y + dy - 1);
+                let open = |vault: &Grid<u8>, point| vault.get(point).is_some_and(|&c| c != WALL);
+                if entrance.x == 0
+                    || entrance.y == 0
+                    || (0..3).any(|dy| (0..3).any(|dx| !open(&vault, around(dx, dy))))
+                {
+                    return Err("the entrance has to be in the middle of an open space".into());
+                }
+
+                for &(dx, dy) in &[(1, 1), (0, 1), (2, 1), (1, 0), (1, 2)] {
+                    vault[around(dx, dy)] = WALL;
+                }
+
+                [around(0, 0), around(2, 0), around(2, 2), around(0, 2)]
+            }
+
+            [a, b, c, d] => [a, b, c, d],
+            _ => return Err("the vault needs one entrance or four".into()),
+        };
+
+        collect_keys(&vault, entrances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part1(vault: &str) -> String {
+        Day18::parse(vault).unwrap().part1().unwrap()
+    }
+
+    fn part2(vault: &str) -> String {
+        Day18::parse(vault).unwrap().part2().unwrap()
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(
+            part1(
+                "\
+#########
+#b.A.@.a#
+#########"
+            ),
+            "8"
+        );
+
+        assert_eq!(
+            part1(
+                "\
+########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################"
+            ),
+            "86"
+        );
+
+        assert_eq!(
+            part1(
+                "\
+########################
+#...............b.C.D.f#
+#.######################
+#.....@.a.B.c.d.A.e.F.g#
+########################"
+            ),
+            "132"
+        );
+
+        assert_eq!(
+            part1(
+                "\
+#################
+#i.G..c...e..H.p#
+########.########
+#j.A..b...f..D.o#
+########@########
+#k.E..a...g..B.n#
+########.########
+#l.F..d...h..C.m#
+#################"
+            ),
+            "136"
+        );
+
+        assert_eq!(
+            part1(
+                "\
+########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################"
+            ),
+            "81"
+        );
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(
+            part2(
+                "\
+#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######"
+            ),
+            "8"
+        );
+
+        assert_eq!(
+            part2(
+                "\
+###############
+#d.ABC.#.....a#
+######@#@######
+###############
+######@#@######
+#b.....#.....c#
+###############"
+            ),
+            "24"
+        );
+
+        assert_eq!(
+            part2(
+                "\
+#############
+#DcBa.#.GhKl#
+#.###@#@#I###
+#e#d#####j#k#
+###C#@#@###J#
+#fEbA.#.FgHi#
+#############"
+            ),
+            "32"
+        );
+
+        assert_eq!(
+            part2(
+                "\
+#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba@#@BcIJ#
+#############
+#nK.L@#@G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############"
+            ),
+            "72"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use common::Solution;
+use intcode::Interpreter;
+
+const PART1_SIDE: u64 = 50;
+const PART2_SIDE: u64 = 100;
+
+/// The drone system, deployed afresh to ask about each point
+pub struct Day19 {
+    program: Interpreter,
+}
+
+impl Day19 {
+    fn in_beam(&self, x: u64, y: u64) -> bool {
+        let mut drone = self.program.clone();
+        drone.input.push_back(x as i64);
+        drone.input.push_back(y as i64);
+
+        // How much of its stack the program uses depends on where it's asked about
+        drone.memory.resize(drone.required_memory(), 0);
+
+        drone.run();
+        drone.output.front() == Some(&1)
+    }
+
+    /// The leftmost point in the beam on row `y` no further left than `from`, if there's one in
+    /// the first `10 * y` columns. Rows close to the emitter can be empty as the beam's too narrow
+    /// to hit any points on them.
+    fn left_edge(&self, from: u64, y: u64) -> Option<u64> {
+        (from..=10 * y).find(|&x| self.in_beam(x, y))
+    }
+}
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            program: Interpreter::parse(input)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok((0..PART1_SIDE)
+            .map(|y| (0..PART1_SIDE).filter(|&x| self.in_beam(x, y)).count())
+            .sum::<usize>()
+            .to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        // Follow the beam's left edge down along the square's bottom row, which only ever moves
+        // right, until the square's top right corner is in the beam too
+        let mut left = 0;
+        for bottom in PART2_SIDE - 1.. {
+            left = match self.left_edge(left, bottom) {
+                Some(left) => left,
+                None => continue,
+            };
+
+            let top = bottom + 1 - PART2_SIDE;
+            if self.in_beam(left + PART2_SIDE - 1, top) {
+                return Ok((10000 * left + top).to_string());
+            }
+        }
+
+        unreachable!()
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    iter::once,
+};
+
+use common::Solution;
+use grid::Grid;
+
+type Point = grid::Point<u8>;
+
+/// Where the maze's outer edge is, which is where its outer portals are
+#[derive(Clone, Copy)]
+struct Edges {
+    upper: u8,
+    lower: u8,
+    left: u8,
+    right: u8,
+}
+
+impl Edges {
+    fn of(empty: &HashSet<Point>) -> Option<Self> {
+        Some(Self {
+            upper: empty.iter().map(|point| point.y).min()?,
+            lower: empty.iter().map(|point| point.y).max()?,
+            left: empty.iter().map(|point| point.x).min()?,
+            right: empty.iter().map(|point| point.x).max()?,
+        })
+    }
+}
+
+// POINT1 -> [(DISTANCE, DEPTH_CHANGE, POINT2)]
+type Graph = HashMap<Point, Vec<(i16, i16, Point)>>;
+
+fn make_graph(
+    empty: &HashSet<Point>,
+    labels: &HashMap<Point, Point>,
+    start: Point,
+    end: Point,
+    depth_change: impl Fn(Point) -> i16,
+) -> Graph {
+    labels
+        .keys()
+        .copied()
+        .chain(once(start))
+        .chain(once(end))
+        .map(|label| {
+            // Walk everywhere reachable from the label, apart from past the end, where the maze is
+            // left
+            let reachable = search::bfs_all(once(label), |&current| {
+                current
+                    .neighbors()
+                    .filter(move |neighbor| current != end && empty.contains(neighbor))
+            });
+
+            let neighbors = reachable
+                .iter()
+                .filter_map(|(&current, distance)| {
+                    let distance = distance as i16;
+                    if current == end {
+                        Some((distance, 0, current))
+                    } else {
+                        let &destination = labels.get(&current)?;
+                        Some((distance + 1, depth_change(current), destination))
+                    }
+                })
+                .collect();
+
+            (label, neighbors)
+        })
+        .collect()
+}
+
+fn heuristic(edges: Edges, d: i16) -> i16 {
+    (edges.right - edges.left) as i16 * d
+}
+
+fn pathfind(graph: Graph, edges: Edges, start: Point, end: Point) -> Option<i16> {
+    // Search through the labels at each depth, never going above the outermost maze
+    let path = search::astar(
+        once((start, 0)),
+        |&(position, d): &(Point, i16)| {
+            graph[&position]
+                .iter()
+                .map(move |&(dg, dd, position)| ((position, d + dd), dg))
+                .filter(|&((_, d), _)| d >= 0)
+        },
+        |&(_, d)| heuristic(edges, d),
+        |&(position, d)| d == 0 && position == end,
+    )?;
+
+    Some(path.cost)
+}
+
+fn is_outer(edges: Edges, Point { x, y }: Point) -> bool {
+    x == edges.left || x == edges.right || y == edges.upper || y == edges.lower
+}
+
+fn depth_change(edges: Edges, label: Point) -> i16 {
+    if is_outer(edges, label) {
+        -1
+    } else {
+        1
+    }
+}
+
+pub struct Day20 {
+    empty: HashSet<Point>,
+    labels: HashMap<Point, Point>,
+    edges: Edges,
+    start: Point,
+    end: Point,
+}
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let map = Grid::parse(input, |c| match c {
+            ' ' | '#' | '.' | 'A'..='Z' => Ok(c as u8),
+            _ => Err(format!("{:?} isn't part of a maze", c)),
+        })?;
+        if map.width() > u8::MAX as usize || map.height() > u8::MAX as usize {
+            return Err("the maze is too big".into());
+        }
+
+        let point = |point: grid::Point<usize>| Point::new(point.x as u8, point.y as u8);
+
+        let mut labels = HashMap::new();
+        let mut unpaired = HashMap::new();
+
+        map.iter()
+            .filter(|(_, c)| c.is_ascii_uppercase())
+            .filter_map(|(position, &c1)| {
+                let position = point(position);
+                let mut c2 = None;
+                let mut trampoline = None;
+                for neighbor in map.neighbors(position) {
+                    match map[neighbor] {
+                        c if c.is_ascii_uppercase() => c2 = Some(c),
+                        b'.' => trampoline = Some(neighbor),
+                        _ => {}
+                    }
+                }
+
+                let c2 = c2?;
+                let name = if c1 < c2 { (c1, c2) } else { (c2, c1) };
+                Some((trampoline?, name))
+            })
+            .for_each(|(pos1, label)| match unpaired.entry(label) {
+                Entry::Occupied(entry) => {
+                    let pos2 = entry.remove();
+                    labels.insert(pos1, pos2);
+                    labels.insert(pos2, pos1);
+                }
+
+                Entry::Vacant(entry) => {
+                    entry.insert(pos1);
+                }
+            });
+
+        let empty = map.positions(b'.').map(point).collect::<HashSet<Point>>();
+
+        let edges = Edges::of(&empty).ok_or("the maze has no passages")?;
+        let start = unpaired.remove(&(b'A', b'A')).ok_or("there's no AA")?;
+        let end = unpaired.remove(&(b'Z', b'Z')).ok_or("there's no ZZ")?;
+
+        Ok(Self {
+            empty,
+            labels,
+            edges,
+            start,
+            end,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        let Self {
+            edges, start, end, ..
+        } = *self;
+
+        let flat = make_graph(&self.empty, &self.labels, start, end, |_| 0);
+        Ok(pathfind(flat, edges, start, end)
+            .ok_or("there's no path from AA to ZZ")?
+            .to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        let Self {
+            edges, start, end, ..
+        } = *self;
+
+        let recursive = make_graph(&self.empty, &self.labels, start, end, |label| {
+            depth_change(edges, label)
+        });
+        Ok(pathfind(recursive, edges, start, end)
+            .ok_or("there's no way out of the maze")?
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST_EXAMPLE: &str = "         A           
+         A           
+  #######.#########  
+  #######.........#  
+  #######.#######.#  
+  #######.#######.#  
+  #######.#######.#  
+  #####  B    ###.#  
+BC...##  C    ###.#  
+  ##.##       ###.#  
+  ##...DE  F  ###.#  
+  #####    G  ###.#  
+  #########.#####.#  
+DE..#######...###.#  
+  #.#########.###.#  
+FG..#########.....#  
+  ###########.#####  
+             Z       
+             Z       ";
+
+    const RECURSIVE_EXAMPLE: &str = "             Z L X W       C                 
+             Z P Q B       K                 
+  ###########.#.#.#.#######.###############  
+  #...#.......#.#.......#.#.......#.#.#...#  
+  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###  
+  #.#...#.#.#...#.#.#...#...#...#.#.......#  
+  #.###.#######.###.###.#.###.###.#.#######  
+  #...#.......#.#...#...#.............#...#  
+  #.#########.#######.#.#######.#######.###  
+  #...#.#    F       R I       Z    #.#.#.#  
+  #.###.#    D       E C       H    #.#.#.#  
+  #.#...#                           #...#.#  
+  #.###.#                           #.###.#  
+  #.#....OA                       WB..#.#..ZH
+  #.###.#                           #.#.#.#  
+CJ......#                           #.....#  
+  #######                           #######  
+  #.#....CK                         #......IC
+  #.###.#                           #.###.#  
+  #.....#                           #...#.#  
+  ###.###                           #.#.#.#  
+XF....#.#                         RF..#.#.#  
+  #####.#                           #######  
+  #......CJ                       NM..#...#  
+  ###.#.#                           #.###.#  
+RE....#.#                           #......RF
+  ###.###        X   X       L      #.#.#.#  
+  #.....#        F   Q       P      #.#.#.#  
+  ###.###########.###.#######.#########.###  
+  #.....#...#.....#.......#...#.....#.#...#  
+  #####.#.###.#######.#######.###.###.#.#.#  
+  #.......#.......#.#.#.#.#...#...#...#.#.#  
+  #####.###.#####.#.#.#.#.###.###.#.###.###  
+  #.......#.....#.#...#...............#...#  
+  #############.#.#.###.###################  
+               A O F   N                     
+               A A D   M                     ";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day20::parse(FIRST_EXAMPLE).unwrap().part1().unwrap(), "23");
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(Day20::parse(FIRST_EXAMPLE).unwrap().part2().unwrap(), "26");
+        assert_eq!(
+            Day20::parse(RECURSIVE_EXAMPLE).unwrap().part2().unwrap(),
+            "396"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use common::Solution;
+use intcode::{ascii, Interpreter};
+
+pub struct Day21 {
+    interpreter: Interpreter,
+}
+
+impl Day21 {
+    /// Walk or run the springdroid with the given springscript, returning the hull damage it reports
+    fn survey(&self, springscript: &str) -> common::Result<String> {
+        let mut interpreter = self.interpreter.clone();
+        interpreter.input_from_ascii(springscript);
+        interpreter.run();
+        Ok(ascii::answer(&interpreter.output)?.to_string())
+    }
+}
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut interpreter = Interpreter::parse(input)?;
+        interpreter.memory.extend_from_slice(&[0; 2000]);
+        Ok(Self { interpreter })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        self.survey(include_str!("springscript1.txt"))
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        self.survey(include_str!("springscript2.txt"))
+    }
+}
@@ -0,0 +1,178 @@
+use common::Solution;
+
+const P1_CARDS: i64 = 10007;
+
+const P2_CARDS: i64 = 119315717514047;
+const P2_REPETITIONS: i64 = 101741582076661;
+
+fn mulmod(mut a: i64, mut b: i64, m: i64) -> i64 {
+    a = a.rem_euclid(m);
+
+    if b < 0 {
+        b += m;
+    }
+
+    let mut res = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            res = (res + a).rem_euclid(m);
+        }
+        a = (2 * a).rem_euclid(m);
+        b >>= 1;
+    }
+    res
+}
+
+fn powmod(mut x: i64, mut y: i64, p: i64) -> i64 {
+    let mut res = 1;
+
+    x = x.rem_euclid(p);
+
+    while y > 0 {
+        if y & 1 != 0 {
+            res = mulmod(res, x, p);
+        }
+
+        y >>= 1;
+        x = mulmod(x, x, p);
+    }
+
+    res
+}
+
+fn invmod(a: i64, m: i64) -> i64 {
+    powmod(a, m - 2, m)
+}
+
+#[derive(Clone, Copy)]
+struct Lcf(i64, i64);
+
+impl Lcf {
+    fn identity() -> Self {
+        Self(1, 0)
+    }
+
+    fn compose(self, other: Self, m: i64) -> Self {
+        Self(
+            mulmod(self.0, other.0, m),
+            (mulmod(self.1, other.0, m) + other.1).rem_euclid(m),
+        )
+    }
+
+    fn apply(self, value: i64, m: i64) -> i64 {
+        (mulmod(self.0, value, m) + self.1).rem_euclid(m)
+    }
+
+    fn repeat(self, k: i64, m: i64) -> Self {
+        let a_k = powmod(self.0, k, m);
+        Self(
+            a_k,
+            mulmod(self.1, mulmod(1 - a_k, invmod(1 - self.0, m), m), m),
+        )
+    }
+
+    fn inverse(self, m: i64) -> Self {
+        let a_inv = invmod(self.0, m);
+        Self(a_inv, -(mulmod(self.1, a_inv, m)))
+    }
+}
+
+fn parse_technique(line: &str) -> Result<Lcf, String> {
+    let n = |prefix| {
+        line.strip_prefix(prefix)
+            .and_then(|n: &str| n.parse::<i64>().ok())
+    };
+
+    if line == "deal into new stack" {
+        Ok(Lcf(-1, -1))
+    } else if let Some(n) = n("cut ") {
+        Ok(Lcf(1, -n))
+    } else if let Some(n) = n("deal with increment ") {
+        Ok(Lcf(n, 0))
+    } else {
+        Err(format!("{:?} isn't a shuffle technique", line))
+    }
+}
+
+fn shuffle(techniques: &[Lcf], m: i64) -> Lcf {
+    techniques
+        .iter()
+        .fold(Lcf::identity(), |a, &b| a.compose(b, m))
+}
+
+pub struct Day22 {
+    techniques: Vec<Lcf>,
+}
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        Ok(Self {
+            techniques: common::parse_lines(input, parse_technique)?,
+        })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(shuffle(&self.techniques, P1_CARDS)
+            .apply(2019, P1_CARDS)
+            .to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(shuffle(&self.techniques, P2_CARDS)
+            .repeat(P2_REPETITIONS, P2_CARDS)
+            .inverse(P2_CARDS)
+            .apply(2020, P2_CARDS)
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The deck of ten cards after the shuffle, from top to bottom
+    fn deck(techniques: &str) -> Vec<i64> {
+        let techniques = common::parse_lines(techniques, parse_technique).unwrap();
+        let shuffle = shuffle(&techniques, 10);
+
+        let mut deck = vec![0; 10];
+        for card in 0..10 {
+            deck[shuffle.apply(card, 10) as usize] = card;
+        }
+        deck
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(
+            deck("deal with increment 7\ndeal into new stack\ndeal into new stack"),
+            [0, 3, 6, 9, 2, 5, 8, 1, 4, 7]
+        );
+        assert_eq!(
+            deck("cut 6\ndeal with increment 7\ndeal into new stack"),
+            [3, 0, 7, 4, 1, 8, 5, 2, 9, 6]
+        );
+        assert_eq!(
+            deck("deal with increment 7\ndeal with increment 9\ncut -2"),
+            [6, 3, 0, 7, 4, 1, 8, 5, 2, 9]
+        );
+        assert_eq!(
+            deck(
+                "\
+deal into new stack
+cut -2
+deal with increment 7
+cut 8
+cut -4
+deal with increment 7
+cut 3
+deal with increment 9
+deal with increment 3
+cut -1"
+            ),
+            [9, 2, 5, 8, 1, 4, 7, 0, 3, 6]
+        );
+    }
+}
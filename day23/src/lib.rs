@@ -0,0 +1,104 @@
+const COMPUTERS: usize = 50;
+const NAT_ADDRESS: usize = 255;
+
+use std::convert::TryFrom;
+
+use common::Solution;
+use intcode::Interpreter;
+
+pub struct Day23 {
+    interpreter: Interpreter,
+}
+
+impl Day23 {
+    /// Run the network until the NAT first wakes it up, or until it sends the same Y twice in a row
+    /// if `repeat` is set, returning that Y
+    fn run(&self, repeat: bool) -> common::Result<i64> {
+        use std::mem::{self, MaybeUninit};
+
+        // To avoid having to allocate a vector on the heap, use a magic incantation to get a
+        // stack-allocated array. Does this affect performance? Probably not. Did I want to do it for
+        // fun? yes!
+        let mut computers = {
+            let mut computers: [MaybeUninit<Interpreter>; COMPUTERS] =
+                unsafe { MaybeUninit::uninit().assume_init() };
+
+            computers.iter_mut().for_each(|elem| {
+                *elem = MaybeUninit::new(self.interpreter.clone());
+            });
+
+            unsafe {
+                mem::transmute::<[MaybeUninit<Interpreter>; COMPUTERS], [Interpreter; COMPUTERS]>(
+                    computers,
+                )
+            }
+        };
+
+        // Send every computer its network ID
+        for (i, computer) in computers.iter_mut().enumerate() {
+            computer.input.push_back(i as i64);
+        }
+
+        // What was the last packet sent to the NAT?
+        let mut nat: Option<(i64, i64)> = None;
+
+        // What was the last Y sent out by the NAT?
+        let mut last_y: Option<i64> = None;
+
+        loop {
+            for i in 0..COMPUTERS {
+                if computers[i].input.is_empty() {
+                    computers[i].input.push_back(-1);
+                }
+
+                computers[i].run();
+
+                let packets: Vec<[i64; 3]> = computers[i].records().collect::<Result<_, _>>()?;
+                for [address, x, y] in packets {
+                    if address == NAT_ADDRESS as i64 {
+                        nat = Some((x, y));
+                    } else {
+                        let computer = usize::try_from(address)
+                            .ok()
+                            .and_then(|address| computers.get_mut(address))
+                            .ok_or_else(|| {
+                                format!("{} isn't the address of a computer", address)
+                            })?;
+                        computer.input.push_back(x);
+                        computer.input.push_back(y);
+                    }
+                }
+            }
+
+            if (0..COMPUTERS).all(|i| computers[i].input.is_empty()) {
+                if let Some((x, y)) = nat.take() {
+                    if (last_y.is_none() && !repeat) || last_y == Some(y) {
+                        return Ok(y);
+                    }
+                    last_y = Some(y);
+
+                    computers[0].input.push_back(x);
+                    computers[0].input.push_back(y);
+                }
+            }
+        }
+    }
+}
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut interpreter = Interpreter::parse(input)?;
+        interpreter.memory.extend_from_slice(&[0; 10]);
+        Ok(Self { interpreter })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        Ok(self.run(false)?.to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(self.run(true)?.to_string())
+    }
+}
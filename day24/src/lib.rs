@@ -0,0 +1,290 @@
+use std::{iter, ops::BitOr};
+
+use animation::{Screen, Tiles};
+use common::Solution;
+use grid::{Direction, Grid};
+
+const GRID_SIDE: i8 = 5;
+
+const MINUTES: usize = 200;
+
+/// Enough layers for bugs to spread up and down into for the minutes they're watched
+const LAYERS: usize = MINUTES + 1;
+
+type Point = grid::Point<i8>;
+
+/// The middle of a layer, which holds the layer below it
+const CENTER: Point = Point::new(GRID_SIDE / 2, GRID_SIDE / 2);
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+struct Layer(u32);
+
+fn mask(Point { x, y }: Point) -> u32 {
+    1 << (y * GRID_SIDE + x)
+}
+
+fn inside(Point { x, y }: Point) -> bool {
+    (0..GRID_SIDE).contains(&x) && (0..GRID_SIDE).contains(&y)
+}
+
+impl Layer {
+    fn set(&mut self, point: Point) {
+        self.0 |= mask(point);
+    }
+
+    fn get(self, point: Point) -> bool {
+        self.0 & mask(point) != 0
+    }
+
+    fn alive_in_mask(self, mask: u32) -> u32 {
+        (self.0 & mask).count_ones()
+    }
+
+    fn alive_total(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    fn biodiversity(self) -> u32 {
+        self.0
+    }
+}
+
+fn four_neighborhood_mask(point: Point) -> u32 {
+    point
+        .neighbors()
+        .filter(|&neighbor| inside(neighbor))
+        .map(mask)
+        .fold(0, BitOr::bitor)
+}
+
+fn plutonian_masks(point: Point) -> (u32, u32, u32) {
+    let mut up_mask = 0;
+    let mut cur_mask = 0;
+    let mut down_mask = 0;
+
+    for &direction in &Direction::ALL {
+        let neighbor = point + direction;
+
+        if neighbor == CENTER {
+            // Moving into the middle lands on the edge of the layer below that's nearest to us
+            let edge = |point: fn(i8) -> Point| (0..GRID_SIDE).map(point).map(mask);
+            down_mask |= match direction {
+                Direction::Right => edge(|y| Point::new(0, y)),
+                Direction::Left => edge(|y| Point::new(GRID_SIDE - 1, y)),
+                Direction::Down => edge(|x| Point::new(x, 0)),
+                Direction::Up => edge(|x| Point::new(x, GRID_SIDE - 1)),
+            }
+            .fold(0, BitOr::bitor);
+        } else if !inside(neighbor) {
+            up_mask |= mask(CENTER + direction);
+        } else {
+            cur_mask |= mask(neighbor);
+        }
+    }
+
+    (up_mask, cur_mask, down_mask)
+}
+
+fn part1_step(layer: Layer) -> Layer {
+    let mut next = Layer(0);
+    for y in 0..GRID_SIDE {
+        for x in 0..GRID_SIDE {
+            let point = Point::new(x, y);
+            let neighbors = layer.alive_in_mask(four_neighborhood_mask(point));
+
+            if neighbors == 1 || (neighbors == 2 && !layer.get(point)) {
+                next.set(point);
+            }
+        }
+    }
+    next
+}
+
+fn part2_step(layers: [Layer; LAYERS]) -> [Layer; LAYERS] {
+    let mut next = [Layer(0); LAYERS];
+    for z in 0..LAYERS {
+        // Ignore patches of three which are empty: these will not change
+        if (z == 0 || layers[z - 1] == Layer(0))
+            && layers[z] == Layer(0)
+            && (z == LAYERS - 1 || layers[z + 1] == Layer(0))
+        {
+            continue;
+        }
+
+        for y in 0..GRID_SIDE {
+            for x in 0..GRID_SIDE {
+                let point = Point::new(x, y);
+                if point == CENTER {
+                    continue;
+                }
+
+                let (up_mask, cur_mask, down_mask) = plutonian_masks(point);
+
+                let up_neighbors = if z == 0 {
+                    0
+                } else {
+                    layers[z - 1].alive_in_mask(up_mask)
+                };
+
+                let down_neighbors = if z == LAYERS - 1 {
+                    0
+                } else {
+                    layers[z + 1].alive_in_mask(down_mask)
+                };
+
+                let neighbors = up_neighbors + layers[z].alive_in_mask(cur_mask) + down_neighbors;
+
+                if neighbors == 1 || (neighbors == 2 && !layers[z].get(point)) {
+                    next[z].set(point);
+                }
+            }
+        }
+    }
+
+    next
+}
+
+pub struct Day24 {
+    layer: Layer,
+}
+
+/// How many layers are drawn side by side before starting another row of them
+const LAYERS_PER_ROW: usize = 16;
+
+/// The tiles layers are drawn with, which are what's between layers, empty space in a layer and bugs
+const BACKGROUND: i64 = 0;
+const SPACE: i64 = 1;
+const BUG: i64 = 2;
+
+/// How each kind of tile is drawn, on the terminal and in images
+const PALETTE: &str = " .#";
+pub const COLORS: [[u8; 3]; 3] = [[0x18, 0x18, 0x20], [0x38, 0x58, 0x30], [0xe0, 0xc0, 0x40]];
+
+/// Draw the layers in rows, from the outermost, with a tile of background around each
+fn draw_layers(layers: &[Layer]) -> Grid<i64> {
+    let side = GRID_SIDE as usize + 1;
+    let columns = layers.len().min(LAYERS_PER_ROW);
+    let rows = layers.len().div_ceil(LAYERS_PER_ROW);
+
+    let mut tiles = Grid::new(columns * side + 1, rows * side + 1, BACKGROUND);
+    for (idx, layer) in layers.iter().enumerate() {
+        let left = 1 + idx % LAYERS_PER_ROW * side;
+        let top = 1 + idx / LAYERS_PER_ROW * side;
+
+        for y in 0..GRID_SIDE {
+            for x in 0..GRID_SIDE {
+                // The middle of each layer is the next layer in, so it's left as background
+                let point = Point::new(x, y);
+                if point == CENTER {
+                    continue;
+                }
+
+                let tile = grid::Point::new(left + x as usize, top + y as usize);
+                tiles[tile] = if layer.get(point) { BUG } else { SPACE };
+            }
+        }
+    }
+
+    tiles
+}
+
+fn bugs(layers: &[Layer; LAYERS]) -> u32 {
+    layers.iter().map(|layer| layer.alive_total()).sum()
+}
+
+impl Day24 {
+    /// Every layer, minute by minute from the start until some minutes have passed, no more than
+    /// `MINUTES`
+    fn recursive_layers(&self, minutes: usize) -> impl Iterator<Item = [Layer; LAYERS]> {
+        let mut layers = [Layer(0); LAYERS];
+        layers[LAYERS / 2] = self.layer;
+
+        iter::successors(Some(layers), |&layers| Some(part2_step(layers))).take(minutes + 1)
+    }
+
+    /// How many bugs there are across every layer after some minutes, no more than `MINUTES`
+    fn bugs_after(&self, minutes: usize) -> u32 {
+        bugs(&self.recursive_layers(minutes).last().unwrap())
+    }
+
+    /// Watch the bugs spread through the layers, returning how many there are once `MINUTES`
+    /// minutes have passed
+    pub fn animate(&self, screen: &mut dyn Screen) -> common::Result<String> {
+        // Only the layers the bugs ever get to are drawn, and every frame draws all of them so
+        // they're all the same size
+        let infested = |layers: [Layer; LAYERS]| {
+            let first = layers.iter().position(|&layer| layer != Layer(0));
+            let last = layers.iter().rposition(|&layer| layer != Layer(0));
+            first.zip(last)
+        };
+        let (first, last) = self
+            .recursive_layers(MINUTES)
+            .filter_map(infested)
+            .fold((LAYERS / 2, LAYERS / 2), |(first, last), (from, to)| {
+                (first.min(from), last.max(to))
+            });
+
+        let mut count = 0;
+        for layers in self.recursive_layers(MINUTES) {
+            screen.draw(&Tiles::new(&draw_layers(&layers[first..=last]), PALETTE));
+            count = bugs(&layers);
+        }
+
+        Ok(count.to_string())
+    }
+}
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let map = Grid::parse(input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("{:?} isn't a bug or empty space", c)),
+        })?;
+        if (map.width(), map.height()) != (GRID_SIDE as usize, GRID_SIDE as usize) {
+            return Err(format!(
+                "expected {}x{} tiles but got {}x{}",
+                GRID_SIDE,
+                GRID_SIDE,
+                map.width(),
+                map.height()
+            )
+            .into());
+        }
+
+        let mut layer = Layer(0);
+        for point in map.positions(true) {
+            layer.set(Point::new(point.x as i8, point.y as i8));
+        }
+
+        Ok(Self { layer })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        let cycle = cycle::hashed(self.layer, |&layer| part1_step(layer));
+        Ok(cycle.state.biodiversity().to_string())
+    }
+
+    fn part2(&self) -> common::Result<String> {
+        Ok(self.bugs_after(MINUTES).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "....#\n#..#.\n#..##\n..#..\n#....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day24::parse(EXAMPLE).unwrap().part1().unwrap(), "2129920");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day24::parse(EXAMPLE).unwrap().bugs_after(10), 99);
+    }
+}
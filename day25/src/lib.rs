@@ -0,0 +1,327 @@
+use std::collections::{HashSet, VecDeque};
+
+use common::Solution;
+use intcode::{ascii, Interpreter};
+
+const NL: i64 = b'\n' as i64;
+
+// if we're in the Security Checkpoint
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+struct Room {
+    name: String,
+    doors: Vec<Door>,
+    items: Vec<String>,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+enum Door {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Door {
+    fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+        }
+    }
+}
+
+impl std::str::FromStr for Door {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "north" => Ok(Self::North),
+            "south" => Ok(Self::South),
+            "east" => Ok(Self::East),
+            "west" => Ok(Self::West),
+            _ => Err(format!("Invalid door {:?}", s)),
+        }
+    }
+}
+
+impl From<Door> for &'static str {
+    fn from(door: Door) -> Self {
+        match door {
+            Door::North => "north",
+            Door::South => "south",
+            Door::East => "east",
+            Door::West => "west",
+        }
+    }
+}
+
+/// Read a list of "- item" lines up until an empty line
+fn list<T>(output: &mut VecDeque<i64>) -> Result<Vec<T>, ascii::Error>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Debug,
+{
+    let mut result = Vec::new();
+    loop {
+        let item = ascii::read_line(output)?;
+
+        if item.is_empty() {
+            break;
+        }
+
+        // Remove the leading "- "
+        result.push(item[2..].parse().unwrap());
+    }
+    Ok(result)
+}
+
+impl Room {
+    fn parse(output: &mut VecDeque<i64>) -> Result<Self, ascii::Error> {
+        // Skip leading newlines
+        while output.front() == Some(&NL) {
+            output.pop_front();
+        }
+
+        // The next line is the room's name.
+        let mut name = ascii::read_line(output)?;
+
+        // Remove the leading/trailing ==
+        name.drain(..3);
+        name.drain(name.len() - 3..);
+
+        // Everything until the next empty line is the room's description. Just ignore it.
+        ascii::read_until(output, "\n\n")?;
+
+        // Until the next empty line, every line is a door
+        ascii::expect(output, "Doors here lead:")?;
+        let doors = list(output)?;
+
+        // If the items are present they're also a list
+        let items = if output.front() == Some(&(b'I' as i64)) {
+            ascii::expect(output, "Items here:")?;
+            list(output)?
+        } else {
+            vec![]
+        };
+
+        if output.front() == Some(&(b'C' as i64)) {
+            ascii::expect(output, "Command?")?;
+        }
+
+        Ok(Room { name, doors, items })
+    }
+}
+
+fn is_safe(item: &str) -> bool {
+    !matches!(
+        item,
+        "photons" | "escape pod" | "infinite loop" | "molten lava" | "giant electromagnet"
+    )
+}
+
+struct Droid {
+    visited_rooms: HashSet<String>,
+    interpreter: Interpreter,
+    items: Vec<String>,
+}
+
+impl Droid {
+    fn new(interpreter: Interpreter) -> Self {
+        Self {
+            interpreter,
+            visited_rooms: HashSet::new(),
+            items: Vec::new(),
+        }
+    }
+
+    fn command(&mut self, command: &str) {
+        ascii::send_line(&mut self.interpreter, command).unwrap();
+        self.interpreter.run();
+    }
+
+    fn take(&mut self, idx: usize) {
+        self.command(&format!("take {}", self.items[idx]));
+        self.interpreter.output.clear();
+    }
+
+    fn drop(&mut self, idx: usize) {
+        self.command(&format!("drop {}", self.items[idx]));
+        self.interpreter.output.clear();
+    }
+
+    fn enter(&mut self, door: Door) {
+        self.command(door.into());
+    }
+
+    fn explore(&mut self, Room { name, items, doors }: Room) {
+        // Don't explore from the Security Checkpoint because of the Pressure-Sensitive Floor
+        if name == "Security Checkpoint" {
+            return;
+        }
+
+        // Don't visit what we've already visited
+        if !self.visited_rooms.insert(name) {
+            return;
+        }
+
+        // Take every item we can
+        for item in items {
+            if is_safe(&item) {
+                self.items.push(item);
+                self.take(self.items.len() - 1);
+            }
+        }
+
+        // Visit every possible room
+        for door in doors.iter().copied() {
+            // Tell the game to visit the room
+            self.enter(door);
+
+            // Parse the information about the room
+            let new_room = Room::parse(&mut self.interpreter.output).unwrap();
+
+            // Keep searching from that room
+            self.explore(new_room);
+
+            // Afterwards, return to the original room
+            self.enter(door.opposite());
+
+            // Ignore the output
+            self.interpreter.output.clear();
+        }
+    }
+}
+
+#[derive(Debug, Copy, PartialEq, Eq, Clone)]
+enum Weight {
+    TooHeavy,
+    TooLight,
+    JustRight,
+}
+
+fn analyse(interpreter: &Interpreter) -> Weight {
+    let s = interpreter.output_as_ascii().collect::<String>();
+
+    if s.contains("heavier") {
+        Weight::TooLight
+    } else if s.contains("lighter") {
+        Weight::TooHeavy
+    } else {
+        Weight::JustRight
+    }
+}
+
+pub struct Day25 {
+    interpreter: Interpreter,
+}
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const PARTS: u32 = 1;
+
+    fn parse(input: &str) -> common::Result<Self> {
+        let mut interpreter = Interpreter::parse(input)?;
+        interpreter.memory.extend_from_slice(&[0; 225]);
+        Ok(Self { interpreter })
+    }
+
+    fn part1(&self) -> common::Result<String> {
+        let mut interpreter = self.interpreter.clone();
+        interpreter.run();
+        let room = Room::parse(&mut interpreter.output)?;
+
+        let mut droid = Droid::new(interpreter);
+
+        // Explore all possible rooms and pick up everything
+        droid.explore(room);
+
+        // Go to the security room. Going west now will make us enter the pressure-sensitive floor
+        droid.enter(Door::South);
+        droid.enter(Door::East);
+        droid.enter(Door::South);
+
+        // Remove all the useless output
+        droid.interpreter.output.clear();
+
+        // Drop all known items
+        (0..droid.items.len()).for_each(|item| droid.drop(item));
+
+        // For all items, iterated in reverse so that we can remove
+        for item in (0..droid.items.len()).rev() {
+            // Get the item from the floor
+            droid.take(item);
+
+            // Enter the pressure-sensitive room
+            droid.enter(Door::West);
+            let weight = analyse(&droid.interpreter);
+            droid.interpreter.output.clear();
+
+            // Drop the item
+            droid.drop(item);
+
+            // If the item makes us too heavy on its own, remove it from consideration
+            if weight == Weight::TooHeavy {
+                droid.items.remove(item);
+            }
+        }
+
+        // Pick everything back up
+        (0..droid.items.len()).for_each(|item| droid.take(item));
+
+        for item in (0..droid.items.len()).rev() {
+            // Drop just one item at a time
+            droid.drop(item);
+
+            // Analyse our weight with everything but that item
+            droid.enter(Door::West);
+            let weight = analyse(&droid.interpreter);
+            droid.interpreter.output.clear();
+
+            // Pick it back up
+            droid.take(item);
+
+            // If we're too light without the item, we need the item to reach the target weight, so
+            // just always keep it and never drop it again
+            if weight == Weight::TooLight {
+                droid.items.remove(item);
+            }
+        }
+
+        // Now, armed with our knowledge of what we mustn't drop ...
+        // Check every possible item set, dropping some of the optional items every time
+        for i in 0u8..(1 << droid.items.len()) {
+            for item in 0..droid.items.len() {
+                if i & (1 << item) != 0 {
+                    droid.drop(item);
+                }
+            }
+
+            droid.enter(Door::West);
+            let weight = analyse(&droid.interpreter);
+
+            if weight == Weight::JustRight {
+                // Santa radios the password for the main airlock in
+                let message = droid.interpreter.output_as_ascii().collect::<String>();
+                return Ok(message
+                    .split_whitespace()
+                    .find(|word| word.bytes().all(|c| c.is_ascii_digit()))
+                    .ok_or_else(|| format!("there's no password in {:?}", message))?
+                    .to_owned());
+            }
+
+            droid.interpreter.output.clear();
+
+            // Otherwise, pick everything back up and try again
+            for item in 0..droid.items.len() {
+                if i & (1 << item) != 0 {
+                    droid.take(item);
+                }
+            }
+        }
+
+        Err("no set of items weighs just right".into())
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    error, fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{Coordinate, Point};
+
+/// Why a map couldn't be parsed into a grid. Rows and columns count from one, like lines do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+
+    /// A row isn't as wide as the first one
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+
+    /// A character isn't anything the map can have, with why
+    Cell {
+        row: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "the map is empty"),
+
+            ParseError::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "row {} is {} wide, but the first row is {} wide",
+                row, width, expected
+            ),
+
+            ParseError::Cell {
+                row,
+                column,
+                message,
+            } => write!(f, "row {}, column {}: {}", row, column, message),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// A rectangular grid with a cell at every point inside it, starting from the origin in its top
+/// left corner
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid filled with copies of a cell
+    pub fn new(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// Parse a map, a row of cells to a line, reading each character as a cell. Every row has to be
+    /// as wide as the others. Blank lines at the map's end are left out, but the spaces at the end
+    /// of its rows aren't.
+    pub fn parse<E: fmt::Display>(
+        map: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(map.len());
+
+        for (y, line) in map.trim_end_matches(&['\n', '\r'][..]).lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(cell(c).map_err(|err| ParseError::Cell {
+                    row: y + 1,
+                    column: x + 1,
+                    message: err.to_string(),
+                })?);
+            }
+
+            let row = cells.len() - before;
+            match width {
+                None => width = Some(row),
+                Some(expected) if row != expected => {
+                    return Err(ParseError::Ragged {
+                        row: y + 1,
+                        width: row,
+                        expected,
+                    })
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width != 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of<C: Coordinate>(&self, point: Point<C>) -> Option<usize> {
+        let Point { x, y } = point.cast::<usize>()?;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains<C: Coordinate>(&self, point: Point<C>) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get<C: Coordinate>(&self, point: Point<C>) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut<C: Coordinate>(&mut self, point: Point<C>) -> Option<&mut T> {
+        self.index_of(point).map(move |idx| &mut self.cells[idx])
+    }
+
+    /// The points next to this one which are inside the grid
+    pub fn neighbors<C: Coordinate>(&self, point: Point<C>) -> impl Iterator<Item = Point<C>> + '_ {
+        point
+            .neighbors()
+            .filter(move |&neighbor| self.contains(neighbor))
+    }
+
+    /// Every point in the grid with its cell, a row at a time from the top
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> + '_ {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| (Point::new(idx % width, idx / width), cell))
+    }
+
+    /// Every point whose cell is the given one
+    pub fn positions(&self, cell: T) -> impl Iterator<Item = Point<usize>> + '_
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |&(_, other)| *other == cell)
+            .map(|(point, _)| point)
+    }
+
+    /// Write the grid back out as a map, a line to a row, drawing each cell as a character. A grid
+    /// with no columns is drawn as nothing at all, like one with no rows.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        if self.width == 0 {
+            return String::new();
+        }
+
+        self.cells
+            .chunks(self.width)
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Indexing outside the grid panics, like indexing outside a slice does
+impl<T, C: Coordinate> Index<Point<C>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<C>) -> &T {
+        match self.index_of(point) {
+            Some(idx) => &self.cells[idx],
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T, C: Coordinate> IndexMut<Point<C>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<C>) -> &mut T {
+        match self.index_of(point) {
+            Some(idx) => &mut self.cells[idx],
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#.#\n..#\n";
+
+    #[test]
+    fn parse_and_render() {
+        let grid = Grid::parse(MAP, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("not a wall or floor"),
+        })
+        .unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Point::<u8>::new(2, 1)]);
+        assert!(!grid[Point::<u8>::new(1, 1)]);
+        assert_eq!(grid.get(Point::<i64>::new(-1, 0)), None);
+        assert_eq!(
+            grid.render(|&wall| if wall { '#' } else { '.' }),
+            MAP.trim_end()
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cell = |c| match c {
+            '#' | '.' => Ok(c),
+            _ => Err(format!("{:?} isn't a wall or floor", c)),
+        };
+
+        assert_eq!(Grid::parse("", cell), Err(ParseError::Empty));
+        assert_eq!(
+            Grid::parse("##\n#\n", cell).unwrap_err().to_string(),
+            "row 2 is 1 wide, but the first row is 2 wide"
+        );
+        assert_eq!(
+            Grid::parse("##\n#?\n", cell).unwrap_err().to_string(),
+            "row 2, column 2: '?' isn't a wall or floor"
+        );
+    }
+
+    #[test]
+    fn render_empty() {
+        assert_eq!(Grid::new(0, 3, false).render(|_| '#'), "");
+        assert_eq!(Grid::new(3, 0, false).render(|_| '#'), "");
+        assert_eq!(Grid::new(1, 2, false).render(|_| '#'), "#\n#");
+    }
+
+    #[test]
+    fn neighbors_stay_inside() {
+        let grid = Grid::new(2, 2, ());
+        let neighbors = grid.neighbors(Point::<i32>::new(1, 0)).collect::<Vec<_>>();
+        assert_eq!(neighbors, [Point::new(1, 1), Point::new(0, 0)]);
+    }
+}
@@ -0,0 +1,12 @@
+//! Points, directions and the grids they live on, for the puzzles played out on maps. Maps are read
+//! a line at a time from the top, so y grows downwards and up is towards the first line.
+
+mod bounded;
+mod direction;
+mod point;
+mod sparse;
+
+pub use bounded::{Grid, ParseError};
+pub use direction::Direction;
+pub use point::{Coordinate, Point};
+pub use sparse::SparseGrid;
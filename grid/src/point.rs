@@ -0,0 +1,191 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    hash::Hash,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+use crate::Direction;
+
+/// An integer type points can have as their coordinates
+pub trait Coordinate:
+    'static + Copy + Ord + Hash + fmt::Debug + Add<Output = Self> + Sub<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// Every coordinate type fits in an `i128`, which makes it the go-between for converting them
+    fn to_i128(self) -> i128;
+    fn from_i128(n: i128) -> Option<Self>;
+}
+
+macro_rules! coordinate {
+    ($($t:ty)*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(n: i128) -> Option<Self> {
+                    Self::try_from(n).ok()
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+/// A point on a grid, whose y coordinate grows downwards like the lines of a map do
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Coordinate> Point<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO);
+
+    /// The point some distance away in the direction, if its coordinates fit in `T`
+    pub fn checked_steps(self, direction: Direction, distance: T) -> Option<Self> {
+        let Self { x, y } = self;
+        Some(match direction {
+            Direction::Up => Self::new(x, y.checked_sub(distance)?),
+            Direction::Right => Self::new(x.checked_add(distance)?, y),
+            Direction::Down => Self::new(x, y.checked_add(distance)?),
+            Direction::Left => Self::new(x.checked_sub(distance)?, y),
+        })
+    }
+
+    /// The point next to this one in the direction, if its coordinates fit in `T`
+    pub fn checked_step(self, direction: Direction) -> Option<Self> {
+        self.checked_steps(direction, T::ONE)
+    }
+
+    /// The points next to this one whose coordinates fit in `T`, clockwise from the one above
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .iter()
+            .filter_map(move |&direction| self.checked_step(direction))
+    }
+
+    /// How far apart the points are walking along the grid
+    pub fn manhattan(self, other: Self) -> T {
+        let distance = |a: T, b: T| a.max(b) - a.min(b);
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// The same point with coordinates of another type, if they fit in it
+    pub fn cast<U: Coordinate>(self) -> Option<Point<U>> {
+        Some(Point::new(
+            U::from_i128(self.x.to_i128())?,
+            U::from_i128(self.y.to_i128())?,
+        ))
+    }
+}
+
+impl<T: Coordinate> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Coordinate> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Coordinate> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Coordinate> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Stepping off the edge of what `T` can hold panics, like overflowing arithmetic does
+impl<T: Coordinate> Add<Direction> for Point<T> {
+    type Output = Self;
+
+    fn add(self, direction: Direction) -> Self {
+        self.checked_step(direction)
+            .unwrap_or_else(|| panic!("stepping {:?} from {:?} overflows", direction, self))
+    }
+}
+
+impl<T: Coordinate> AddAssign<Direction> for Point<T> {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_stay_in_range() {
+        let corner = Point::<u8>::ORIGIN.neighbors().collect::<Vec<_>>();
+        assert_eq!(corner, [Point::new(1, 0), Point::new(0, 1)]);
+
+        let middle = Point::<i32>::new(3, 3).neighbors().collect::<Vec<_>>();
+        assert_eq!(
+            middle,
+            [
+                Point::new(3, 2),
+                Point::new(4, 3),
+                Point::new(3, 4),
+                Point::new(2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn manhattan() {
+        assert_eq!(Point::<u16>::new(1, 8).manhattan(Point::new(4, 2)), 9);
+        assert_eq!(Point::<i64>::new(-3, 2).manhattan(Point::new(3, -2)), 10);
+    }
+
+    #[test]
+    fn cast() {
+        assert_eq!(Point::<i64>::new(3, 4).cast::<u8>(), Some(Point::new(3, 4)));
+        assert_eq!(Point::<i64>::new(-1, 4).cast::<usize>(), None);
+        assert_eq!(Point::<i64>::new(300, 4).cast::<u8>(), None);
+    }
+}
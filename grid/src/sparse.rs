@@ -0,0 +1,150 @@
+use std::{
+    collections::{hash_map, HashMap},
+    iter::FromIterator,
+};
+
+use crate::{Coordinate, Point};
+
+/// A grid without edges, which only holds the cells put into it
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T, C = i64> {
+    cells: HashMap<Point<C>, T>,
+}
+
+impl<T, C: Coordinate> Default for SparseGrid<T, C> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T, C: Coordinate> SparseGrid<T, C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point<C>) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point<C>) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point<C>) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Put a cell at the point, returning the one that was there
+    pub fn insert(&mut self, point: Point<C>, cell: T) -> Option<T> {
+        self.cells.insert(point, cell)
+    }
+
+    pub fn remove(&mut self, point: Point<C>) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn entry(&mut self, point: Point<C>) -> hash_map::Entry<'_, Point<C>, T> {
+        self.cells.entry(point)
+    }
+
+    /// Every point with a cell, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point<C>, &T)> + '_ {
+        self.cells.iter().map(|(&point, cell)| (point, cell))
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle holding every cell, if
+    /// there are any
+    pub fn bounds(&self) -> Option<(Point<C>, Point<C>)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), point| {
+            (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        }))
+    }
+
+    /// Write the smallest rectangle holding every cell out as a map, a line to a row, drawing each
+    /// point as a character whether or not it has a cell
+    pub fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        // Step through the coordinates without ever going past `max`, which could overflow
+        let range = |from: C, to: C| {
+            let mut next = Some(from);
+            std::iter::from_fn(move || {
+                let current = next?;
+                next = if current < to {
+                    current.checked_add(C::ONE)
+                } else {
+                    None
+                };
+                Some(current)
+            })
+        };
+
+        range(min.y, max.y)
+            .map(|y| {
+                range(min.x, max.x)
+                    .map(|x| cell(self.get(Point::new(x, y))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T, C: Coordinate> FromIterator<(Point<C>, T)> for SparseGrid<T, C> {
+    fn from_iter<I: IntoIterator<Item = (Point<C>, T)>>(cells: I) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+        }
+    }
+}
+
+impl<T, C: Coordinate> Extend<(Point<C>, T)> for SparseGrid<T, C> {
+    fn extend<I: IntoIterator<Item = (Point<C>, T)>>(&mut self, cells: I) {
+        self.cells.extend(cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_bounds() {
+        let grid = vec![(Point::new(-1, 2), 'a'), (Point::new(1, 3), 'b')]
+            .into_iter()
+            .collect::<SparseGrid<_>>();
+
+        assert_eq!(grid.bounds(), Some((Point::new(-1, 2), Point::new(1, 3))));
+        assert_eq!(grid.render(|cell| cell.copied().unwrap_or('.')), "a..\n..b");
+        assert_eq!(SparseGrid::<char>::new().render(|_| '.'), "");
+    }
+
+    #[test]
+    fn render_at_the_edge_of_the_coordinates() {
+        let mut grid = SparseGrid::<bool, u8>::new();
+        grid.insert(Point::new(u8::MAX, 0), true);
+        grid.insert(Point::new(u8::MAX - 1, 0), false);
+        assert_eq!(
+            grid.render(|cell| if cell == Some(&true) { '#' } else { '.' }),
+            ".#"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Generates the C header from the declarations in `src/lib.rs`. It only understands the few
+//! shapes of declaration the library uses, and panics on anything else so that it can't silently
+//! fall out of date. The checked in copy in `include/` is compared against it by the tests.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+/// The C spelling of a Rust type
+fn c_type(rust: &str) -> &'static str {
+    match rust {
+        "" => "void",
+        "bool" => "bool",
+        "i64" => "int64_t",
+        "usize" => "size_t",
+        "Status" => "intcode_status_t",
+        "*const i64" => "const int64_t *",
+        "*mut i64" => "int64_t *",
+        "*const Machine" => "const intcode_t *",
+        "*mut Machine" => "intcode_t *",
+        _ => panic!("no C type for {:?}", rust),
+    }
+}
+
+/// Join a C type and a name, keeping pointer stars next to the name
+fn declare(ty: &str, name: &str) -> String {
+    if ty.ends_with('*') {
+        format!("{}{}", ty, name)
+    } else {
+        format!("{} {}", ty, name)
+    }
+}
+
+/// The C name of an enum variant, such as `INTCODE_WAITING` for `Status::Waiting`
+fn constant(variant: &str) -> String {
+    format!("INTCODE_{}", variant.to_uppercase())
+}
+
+fn comment(header: &mut String, indent: &str, docs: &[&str]) {
+    for line in docs {
+        let line = line.replace("`", "");
+        writeln!(header, "{}//{}", indent, line).unwrap();
+    }
+}
+
+fn function(header: &mut String, docs: &[&str], declaration: &str) {
+    let open = declaration.find('(').unwrap();
+    let close = declaration.rfind(')').unwrap();
+    let name = declaration[..open].trim_start_matches("pub unsafe extern \"C\" fn ");
+
+    let params: Vec<_> = declaration[open + 1..close]
+        .split(',')
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (name, ty) = param.split_at(param.find(':').unwrap());
+            declare(c_type(ty[1..].trim()), name)
+        })
+        .collect();
+
+    let returns = declaration[close + 1..]
+        .trim_end_matches('{')
+        .trim()
+        .trim_start_matches("->")
+        .trim();
+
+    comment(header, "", docs);
+    let params = if params.is_empty() {
+        "void".to_owned()
+    } else {
+        params.join(", ")
+    };
+    writeln!(header, "{}({});\n", declare(c_type(returns), name), params).unwrap();
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+
+    let source = fs::read_to_string("src/lib.rs").unwrap();
+    let mut lines = source.lines().map(str::trim);
+
+    let mut header = String::new();
+    header +=
+        "// Generated from intcode-ffi's src/lib.rs by its build script, don't edit by hand\n\n";
+    header += "#ifndef INTCODE_H\n#define INTCODE_H\n\n";
+    header += "#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n\n";
+    header += "#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n";
+
+    let mut docs = Vec::new();
+    while let Some(line) = lines.next() {
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc);
+            continue;
+        }
+
+        if line == "pub struct Machine {" {
+            comment(&mut header, "", &docs);
+            header += "typedef struct intcode intcode_t;\n\n";
+        } else if line == "pub enum Status {" {
+            comment(&mut header, "", &docs);
+            header += "typedef enum {\n";
+
+            let mut variant_docs = Vec::new();
+            for line in lines.by_ref().take_while(|&line| line != "}") {
+                if let Some(doc) = line.strip_prefix("///") {
+                    variant_docs.push(doc);
+                } else if let Some(variant) = line.strip_suffix(',') {
+                    let (name, value) = variant.split_at(variant.find(" = ").unwrap());
+                    comment(&mut header, "    ", &variant_docs);
+                    writeln!(header, "    {}{},", constant(name), value).unwrap();
+                    variant_docs.clear();
+                }
+            }
+
+            header += "} intcode_status_t;\n\n";
+        } else if line.starts_with("pub unsafe extern \"C\" fn ") {
+            let mut declaration = line.to_owned();
+            while !declaration.ends_with('{') {
+                declaration += lines.next().unwrap();
+            }
+            function(&mut header, &docs, &declaration);
+        } else if line.starts_with("pub ") {
+            panic!("don't know how to declare {:?} in C", line);
+        }
+
+        // Attributes sit between a declaration's docs and the declaration itself
+        if !line.starts_with("#[") {
+            docs.clear();
+        }
+    }
+
+    header += "#ifdef __cplusplus\n}\n#endif\n\n#endif\n";
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("intcode.h"), header).unwrap();
+}
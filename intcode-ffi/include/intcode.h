@@ -0,0 +1,74 @@
+// Generated from intcode-ffi's src/lib.rs by its build script, don't edit by hand
+
+#ifndef INTCODE_H
+#define INTCODE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// An interpreter along with whether it's faulted, opaque to C
+typedef struct intcode intcode_t;
+
+// What a machine is doing
+typedef enum {
+    // The machine can run, either because it hasn't yet or because it's waiting for input
+    INTCODE_WAITING = 0,
+    // The program has halted
+    INTCODE_HALTED = 1,
+    // The program did something invalid and can't run any more
+    INTCODE_FAULTED = 2,
+} intcode_status_t;
+
+// Create a machine running a copy of the len values at program, with its memory padded with
+// zeroes to at least memory cells. Returns NULL if program is NULL and len isn't zero.
+intcode_t *intcode_new(const int64_t *program, size_t len, size_t memory);
+
+// Create an independent copy of a machine, with the same memory, input and output
+intcode_t *intcode_clone(const intcode_t *machine);
+
+// Free a machine. Does nothing if machine is NULL.
+void intcode_free(intcode_t *machine);
+
+// Queue a value as the program's input
+void intcode_push_input(intcode_t *machine, int64_t value);
+
+// Run the program until it halts, needs more input or faults
+intcode_status_t intcode_run(intcode_t *machine);
+
+// What the machine is doing
+intcode_status_t intcode_status(const intcode_t *machine);
+
+// Take the oldest value from the program's output into *value, returning whether there was one
+bool intcode_pop_output(intcode_t *machine, int64_t *value);
+
+// The number of values waiting in the program's output
+size_t intcode_output_len(const intcode_t *machine);
+
+// The number of values queued as input which the program hasn't read yet
+size_t intcode_input_len(const intcode_t *machine);
+
+// The address of the next instruction to execute
+size_t intcode_pc(const intcode_t *machine);
+
+// The base which relative parameters are offset from
+int64_t intcode_relative_base(const intcode_t *machine);
+
+// The number of cells in the machine's memory
+size_t intcode_memory_len(const intcode_t *machine);
+
+// Read the cell at address into *value, returning whether it's in memory
+bool intcode_peek(const intcode_t *machine, size_t address, int64_t *value);
+
+// Write value into the cell at address, returning whether it's in memory
+bool intcode_poke(intcode_t *machine, size_t address, int64_t value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,188 @@
+//! A C interface to the interpreter, built as `libintcode_ffi`, for tools in other languages
+//! which want to run intcode programs. The declarations are in `include/intcode.h`, which is
+//! generated from this file by the build script.
+//!
+//! Every function taking an `intcode_t *` requires it to be a live handle returned by
+//! `intcode_new` or `intcode_clone`, and not used by another thread at the same time. Other
+//! pointers must be valid for what the function does with them.
+
+// The safety requirements are the same for every function, so they're documented once above
+#![allow(clippy::missing_safety_doc)]
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+use intcode::Interpreter;
+
+/// An interpreter along with whether it's faulted, opaque to C
+#[derive(Clone)]
+pub struct Machine {
+    interpreter: Interpreter,
+
+    /// Whether the program did something the interpreter couldn't handle, such as executing an
+    /// invalid instruction or addressing memory out of bounds
+    faulted: bool,
+}
+
+/// What a machine is doing
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The machine can run, either because it hasn't yet or because it's waiting for input
+    Waiting = 0,
+
+    /// The program has halted
+    Halted = 1,
+
+    /// The program did something invalid and can't run any more
+    Faulted = 2,
+}
+
+/// Create a machine running a copy of the `len` values at `program`, with its memory padded with
+/// zeroes to at least `memory` cells. Returns NULL if `program` is NULL and `len` isn't zero.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_new(
+    program: *const i64,
+    len: usize,
+    memory: usize,
+) -> *mut Machine {
+    let program = match (program.is_null(), len) {
+        (true, 0) => &[][..],
+        (true, _) => return std::ptr::null_mut(),
+        (false, _) => slice::from_raw_parts(program, len),
+    };
+
+    let mut cells = program.to_vec();
+    if cells.len() < memory {
+        cells.resize(memory, 0);
+    }
+
+    Box::into_raw(Box::new(Machine {
+        interpreter: Interpreter::new(cells),
+        faulted: false,
+    }))
+}
+
+/// Create an independent copy of a machine, with the same memory, input and output
+#[no_mangle]
+pub unsafe extern "C" fn intcode_clone(machine: *const Machine) -> *mut Machine {
+    Box::into_raw(Box::new((*machine).clone()))
+}
+
+/// Free a machine. Does nothing if `machine` is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_free(machine: *mut Machine) {
+    if !machine.is_null() {
+        drop(Box::from_raw(machine));
+    }
+}
+
+/// Queue a value as the program's input
+#[no_mangle]
+pub unsafe extern "C" fn intcode_push_input(machine: *mut Machine, value: i64) {
+    (*machine).interpreter.input.push_back(value);
+}
+
+/// Run the program until it halts, needs more input or faults
+#[no_mangle]
+pub unsafe extern "C" fn intcode_run(machine: *mut Machine) -> Status {
+    let machine = &mut *machine;
+    if !machine.faulted && !machine.interpreter.done {
+        // Stepping checks every instruction, where running trusts the program to be valid
+        let interpreter = &mut machine.interpreter;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| while interpreter.step() {}));
+        machine.faulted = result.is_err();
+    }
+
+    status(machine)
+}
+
+fn status(machine: &Machine) -> Status {
+    if machine.faulted {
+        Status::Faulted
+    } else if machine.interpreter.done {
+        Status::Halted
+    } else {
+        Status::Waiting
+    }
+}
+
+/// What the machine is doing
+#[no_mangle]
+pub unsafe extern "C" fn intcode_status(machine: *const Machine) -> Status {
+    status(&*machine)
+}
+
+/// Take the oldest value from the program's output into `*value`, returning whether there was one
+#[no_mangle]
+pub unsafe extern "C" fn intcode_pop_output(machine: *mut Machine, value: *mut i64) -> bool {
+    match (*machine).interpreter.output.pop_front() {
+        Some(output) => {
+            *value = output;
+            true
+        }
+        None => false,
+    }
+}
+
+/// The number of values waiting in the program's output
+#[no_mangle]
+pub unsafe extern "C" fn intcode_output_len(machine: *const Machine) -> usize {
+    (*machine).interpreter.output.len()
+}
+
+/// The number of values queued as input which the program hasn't read yet
+#[no_mangle]
+pub unsafe extern "C" fn intcode_input_len(machine: *const Machine) -> usize {
+    (*machine).interpreter.input.len()
+}
+
+/// The address of the next instruction to execute
+#[no_mangle]
+pub unsafe extern "C" fn intcode_pc(machine: *const Machine) -> usize {
+    (*machine).interpreter.pc
+}
+
+/// The base which relative parameters are offset from
+#[no_mangle]
+pub unsafe extern "C" fn intcode_relative_base(machine: *const Machine) -> i64 {
+    (*machine).interpreter.relative_base
+}
+
+/// The number of cells in the machine's memory
+#[no_mangle]
+pub unsafe extern "C" fn intcode_memory_len(machine: *const Machine) -> usize {
+    (*machine).interpreter.memory.len()
+}
+
+/// Read the cell at `address` into `*value`, returning whether it's in memory
+#[no_mangle]
+pub unsafe extern "C" fn intcode_peek(
+    machine: *const Machine,
+    address: usize,
+    value: *mut i64,
+) -> bool {
+    let machine = &*machine;
+    match machine.interpreter.memory.get(address) {
+        Some(&cell) => {
+            *value = cell;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Write `value` into the cell at `address`, returning whether it's in memory
+#[no_mangle]
+pub unsafe extern "C" fn intcode_poke(machine: *mut Machine, address: usize, value: i64) -> bool {
+    let machine = &mut *machine;
+    match machine.interpreter.memory.get_mut(address) {
+        Some(cell) => {
+            *cell = value;
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,54 @@
+use std::{env, fs, path::Path, process::Command};
+
+const HEADER: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/include/intcode.h");
+
+/// The checked in header has to match what the build script generates. Running the tests with
+/// `INTCODE_BLESS` set updates it.
+#[test]
+fn header_is_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/intcode.h"));
+    if env::var_os("INTCODE_BLESS").is_some() {
+        fs::write(HEADER, generated).unwrap();
+    }
+
+    let checked_in = fs::read_to_string(HEADER).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "include/intcode.h is out of date, run the tests with INTCODE_BLESS=1 to update it"
+    );
+}
+
+/// Compile the C test program against the library and run it
+#[test]
+fn c_program() {
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+    if Command::new(&compiler).arg("--version").output().is_err() {
+        eprintln!("skipping as there's no C compiler, set CC to use one");
+        return;
+    }
+
+    // The library is built into the same directory as the tests
+    let test = env::current_exe().unwrap();
+    let library = test.parent().unwrap();
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("intcode-ffi-c");
+
+    let status = Command::new(&compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&program)
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .arg(manifest.join("tests/c/main.c"))
+        .arg(format!("-L{}", library.display()))
+        .arg(format!("-Wl,-rpath,{}", library.display()))
+        .arg("-lintcode_ffi")
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling the C program failed");
+
+    let status = Command::new(&program)
+        .arg(manifest.join("../inputs/day05.txt"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "the C program failed");
+}
@@ -0,0 +1,141 @@
+// Exercises the C interface the way a C program would, exiting unsuccessfully if anything's wrong.
+// Takes the path of day 5's input as its argument.
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "intcode.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                                           \
+    do {                                                                                           \
+        if (!(condition)) {                                                                        \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition);          \
+            failures++;                                                                            \
+        }                                                                                          \
+    } while (0)
+
+// Reads a comma separated program, returning how many values were read
+static size_t read_program(const char *path, int64_t *program, size_t capacity) {
+    FILE *file = fopen(path, "r");
+    if (file == NULL) {
+        return 0;
+    }
+
+    size_t len = 0;
+    long long value;
+    while (len < capacity && fscanf(file, "%lld,", &value) == 1) {
+        program[len++] = value;
+    }
+
+    fclose(file);
+    return len;
+}
+
+// Outputs its own code, needing memory beyond the program
+static void quine(void) {
+    const int64_t program[] = {109, 1,   204, -1,  1001, 100, 1, 100,
+                               1008, 100, 16, 101, 1006, 101, 0, 99};
+    const size_t len = sizeof program / sizeof program[0];
+
+    intcode_t *machine = intcode_new(program, len, 1000);
+    CHECK(intcode_memory_len(machine) == 1000);
+    CHECK(intcode_run(machine) == INTCODE_HALTED);
+    CHECK(intcode_output_len(machine) == len);
+
+    for (size_t i = 0; i < len; i++) {
+        int64_t value = 0;
+        CHECK(intcode_pop_output(machine, &value));
+        CHECK(value == program[i]);
+    }
+
+    int64_t value;
+    CHECK(!intcode_pop_output(machine, &value));
+    intcode_free(machine);
+}
+
+// Waits for input, which a clone gets separately
+static void echo(void) {
+    const int64_t program[] = {3, 9, 4, 9, 1105, 1, 0, 99, 0, 0};
+    intcode_t *machine = intcode_new(program, 10, 0);
+
+    CHECK(intcode_status(machine) == INTCODE_WAITING);
+    CHECK(intcode_run(machine) == INTCODE_WAITING);
+    CHECK(intcode_pc(machine) == 0);
+    CHECK(intcode_output_len(machine) == 0);
+
+    intcode_push_input(machine, 42);
+    CHECK(intcode_input_len(machine) == 1);
+    intcode_t *clone = intcode_clone(machine);
+    intcode_push_input(clone, 7);
+
+    int64_t value = 0;
+    CHECK(intcode_run(machine) == INTCODE_WAITING);
+    CHECK(intcode_pop_output(machine, &value) && value == 42);
+    CHECK(!intcode_pop_output(machine, &value));
+
+    CHECK(intcode_run(clone) == INTCODE_WAITING);
+    CHECK(intcode_pop_output(clone, &value) && value == 42);
+    CHECK(intcode_pop_output(clone, &value) && value == 7);
+
+    // Turning the jump back into a halt lets the program end
+    CHECK(intcode_poke(machine, 4, 99));
+    CHECK(intcode_peek(machine, 4, &value) && value == 99);
+    CHECK(!intcode_peek(machine, 10, &value));
+    CHECK(!intcode_poke(machine, 10, 0));
+    intcode_push_input(machine, 1);
+    CHECK(intcode_run(machine) == INTCODE_HALTED);
+    CHECK(intcode_pc(machine) == 4);
+
+    intcode_free(machine);
+    intcode_free(clone);
+}
+
+// Executes an invalid instruction, then addresses memory out of bounds
+static void faults(void) {
+    const int64_t invalid[] = {109, 5, 42, 99};
+    intcode_t *machine = intcode_new(invalid, 4, 0);
+    CHECK(intcode_run(machine) == INTCODE_FAULTED);
+    CHECK(intcode_relative_base(machine) == 5);
+    CHECK(intcode_run(machine) == INTCODE_FAULTED);
+    intcode_free(machine);
+
+    const int64_t out_of_bounds[] = {4, 100, 99};
+    machine = intcode_new(out_of_bounds, 3, 0);
+    CHECK(intcode_run(machine) == INTCODE_FAULTED);
+    CHECK(intcode_status(machine) == INTCODE_FAULTED);
+    intcode_free(machine);
+
+    CHECK(intcode_new(NULL, 1, 0) == NULL);
+    intcode_free(NULL);
+}
+
+// Runs a real program, day 5's thermal radiator diagnostic
+static void diagnostic(const char *path) {
+    static int64_t program[4096];
+    size_t len = read_program(path, program, 4096);
+    CHECK(len > 0);
+
+    intcode_t *machine = intcode_new(program, len, 0);
+    intcode_push_input(machine, 5);
+    CHECK(intcode_run(machine) == INTCODE_HALTED);
+
+    int64_t code = 0;
+    CHECK(intcode_pop_output(machine, &code) && code == 14340395);
+    intcode_free(machine);
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <day 5 input>\n", argv[0]);
+        return 2;
+    }
+
+    quine();
+    echo();
+    faults();
+    diagnostic(argv[1]);
+
+    return failures == 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+}
@@ -0,0 +1,278 @@
+use std::{
+    env, fs,
+    io::{self, BufRead, Write},
+    process,
+};
+
+use intcode::{
+    decode::{Instruction, Mode},
+    Interpreter,
+};
+
+const USAGE: &str = "usage: intcode-run [options] <program>
+
+Runs an intcode program, giving it input from stdin and printing its output to stdout. Memory
+grows as the program needs it.
+
+options:
+    --set <address>=<value>  store the value into memory before running, can be repeated
+    --ascii                  read and write text rather than integers
+    --fuel <instructions>    stop after executing this many instructions
+    --trace                  print each instruction executed to stderr
+
+exit codes:
+    0  the program halted
+    1  the program or its input couldn't be read
+    2  the arguments are wrong
+    3  the program wanted more input than stdin had
+    4  the program ran out of fuel
+    5  the program executed an invalid instruction or addressed invalid memory";
+
+/// The most memory a program can grow to, so that a stray address can't take all of the host's
+const MAX_MEMORY: usize = 1 << 26;
+
+/// Enough room past the program counter for the longest instruction
+const LONGEST_INSTRUCTION: usize = 4;
+
+struct Options {
+    program: String,
+    patches: Vec<(usize, i64)>,
+    ascii: bool,
+    fuel: Option<u64>,
+    trace: bool,
+}
+
+/// Why the program stopped running
+enum Stop {
+    Halted,
+    NoInput,
+    OutOfFuel,
+    Fault(String),
+}
+
+impl Stop {
+    fn exit_code(&self) -> i32 {
+        match self {
+            Stop::Halted => 0,
+            Stop::NoInput => 3,
+            Stop::OutOfFuel => 4,
+            Stop::Fault(_) => 5,
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut program = None;
+    let mut options = Options {
+        program: String::new(),
+        patches: Vec::new(),
+        ascii: false,
+        fuel: None,
+        trace: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+
+        match arg.as_str() {
+            "--set" => {
+                let patch = value("--set")?;
+                let parsed = patch
+                    .split_once('=')
+                    .and_then(|(idx, value)| Some((idx.parse().ok()?, value.parse().ok()?)));
+                options
+                    .patches
+                    .push(parsed.ok_or(format!("expected <address>=<value> but got {:?}", patch))?);
+            }
+
+            "--fuel" => {
+                let fuel = value("--fuel")?;
+                options.fuel = Some(
+                    fuel.parse()
+                        .map_err(|_| format!("{:?} isn't an amount of fuel", fuel))?,
+                );
+            }
+
+            "--ascii" => options.ascii = true,
+            "--trace" => options.trace = true,
+
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ if program.is_some() => return Err("only one program can be run".to_owned()),
+            _ => program = Some(arg),
+        }
+    }
+
+    options.program = program.ok_or("no program given")?;
+    Ok(options)
+}
+
+/// Read a line of stdin into the program's input, returning whether there was one
+fn feed(interpreter: &mut Interpreter, stdin: &mut impl BufRead, ascii: bool) -> io::Result<bool> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if stdin.read_line(&mut line)? == 0 {
+            return Ok(false);
+        }
+
+        if ascii {
+            interpreter.input_from_ascii(&line);
+            return Ok(true);
+        }
+
+        for value in line.split(|c: char| c == ',' || c.is_whitespace()) {
+            if value.is_empty() {
+                continue;
+            }
+
+            let value = value.parse().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{:?} isn't an integer", value),
+                )
+            })?;
+            interpreter.input.push_back(value);
+        }
+
+        if !interpreter.input.is_empty() {
+            return Ok(true);
+        }
+    }
+}
+
+/// Print and clear the program's output
+fn drain(interpreter: &mut Interpreter, stdout: &mut impl Write, ascii: bool) -> io::Result<()> {
+    for value in interpreter.output.drain(..) {
+        match value {
+            0..=127 if ascii => stdout.write_all(&[value as u8])?,
+            _ => writeln!(stdout, "{}", value)?,
+        }
+    }
+    Ok(())
+}
+
+/// Grow memory to fit the instruction at the program counter and every cell it addresses,
+/// returning the instruction
+fn prepare(interpreter: &mut Interpreter) -> Result<Instruction, String> {
+    // Jumping to a negative address leaves the program counter just short of overflowing
+    let pc = interpreter.pc;
+    let end = pc
+        .checked_add(LONGEST_INSTRUCTION)
+        .filter(|&end| end <= MAX_MEMORY)
+        .ok_or_else(|| format!("jumped to address {}, which is out of bounds", pc as i64))?;
+    if end > interpreter.memory.len() {
+        interpreter.memory.resize(end, 0);
+    }
+
+    let instruction = Instruction::decode(&interpreter.memory, pc).ok_or_else(|| {
+        format!(
+            "invalid instruction {} at address {}",
+            interpreter.memory[pc], pc
+        )
+    })?;
+
+    for param in instruction.params() {
+        let address = match param.mode {
+            Mode::Immediate => continue,
+            Mode::Position => param.value,
+            Mode::Relative => interpreter.relative_base + param.value,
+        };
+
+        if address < 0 || address as usize >= MAX_MEMORY {
+            return Err(format!(
+                "{} addresses {}, which is out of bounds, at address {}",
+                instruction, address, pc
+            ));
+        }
+
+        if address as usize >= interpreter.memory.len() {
+            interpreter.memory.resize(address as usize + 1, 0);
+        }
+    }
+
+    Ok(instruction)
+}
+
+fn run(interpreter: &mut Interpreter, options: &Options) -> io::Result<Stop> {
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    let stdout = io::stdout();
+    let mut stdout = io::BufWriter::new(stdout.lock());
+
+    let mut fuel = options.fuel;
+    let stop = loop {
+        let pc = interpreter.pc;
+        let instruction = match prepare(interpreter) {
+            Ok(instruction) => instruction,
+            Err(message) => break Stop::Fault(message),
+        };
+
+        if fuel == Some(0) {
+            break Stop::OutOfFuel;
+        }
+
+        let executed = interpreter.step();
+        if options.trace && (executed || interpreter.done) {
+            eprintln!("{:>6}: {}", pc, instruction);
+        }
+
+        if executed {
+            fuel = fuel.map(|fuel| fuel - 1);
+            drain(interpreter, &mut stdout, options.ascii)?;
+        } else if interpreter.done {
+            break Stop::Halted;
+        } else {
+            // Whatever the program said is usually a prompt for what it wants
+            stdout.flush()?;
+            if !feed(interpreter, &mut stdin, options.ascii)? {
+                break Stop::NoInput;
+            }
+        }
+    };
+
+    stdout.flush()?;
+    Ok(stop)
+}
+
+fn main() {
+    let options = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    let program = fs::read_to_string(&options.program).unwrap_or_else(|err| {
+        eprintln!("could not read {}: {}", options.program, err);
+        process::exit(1);
+    });
+
+    let mut interpreter = Interpreter::parse(&program).unwrap_or_else(|err| {
+        eprintln!("could not parse {}: {}", options.program, err);
+        process::exit(1);
+    });
+
+    for &(idx, value) in &options.patches {
+        if idx >= MAX_MEMORY {
+            eprintln!("can't set address {} as it's out of bounds", idx);
+            process::exit(2);
+        }
+
+        if idx >= interpreter.memory.len() {
+            interpreter.memory.resize(idx + 1, 0);
+        }
+        interpreter.memory[idx] = value;
+    }
+
+    let stop = run(&mut interpreter, &options).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    match &stop {
+        Stop::Halted => {}
+        Stop::NoInput => eprintln!("the program wants more input at address {}", interpreter.pc),
+        Stop::OutOfFuel => eprintln!("out of fuel at address {}", interpreter.pc),
+        Stop::Fault(message) => eprintln!("{}", message),
+    }
+
+    process::exit(stop.exit_code());
+}
@@ -0,0 +1,108 @@
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+/// How intcode-run finished
+#[derive(Debug)]
+struct Run {
+    code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+/// Run intcode-run on the program, named so that tests running at the same time don't share a
+/// file, with some arguments and stdin
+fn execute(name: &str, program: &str, args: &[&str], stdin: &str) -> Run {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("intcode-run-{}.txt", name));
+    fs::write(&path, program).unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_intcode-run"))
+        .args(args)
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    Run {
+        code: output.status.code().unwrap(),
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+    }
+}
+
+#[test]
+fn echoes_input() {
+    let run = execute("echo", "3,0,4,0,3,0,4,0,99", &[], "7\n-3\n");
+    assert_eq!((run.code, run.stdout.as_str()), (0, "7\n-3\n"));
+}
+
+#[test]
+fn set() {
+    // The address is past the end of the program, so memory grows to take it
+    let run = execute("set", "4,5,99", &["--set", "5=42", "--set", "1=2"], "");
+    assert_eq!((run.code, run.stdout.as_str()), (0, "99\n"));
+
+    let run = execute("set", "4,5,99", &["--set", "5=42"], "");
+    assert_eq!((run.code, run.stdout.as_str()), (0, "42\n"));
+
+    let run = execute("set", "99", &["--set", "five=42"], "");
+    assert_eq!(run.code, 2, "{:?}", run);
+}
+
+#[test]
+fn ascii() {
+    // Outputs the first character of each line it's given and a newline, then something that
+    // isn't ASCII
+    let program = "3,20,4,20,104,10,3,20,3,20,3,20,4,20,104,10,104,1000,99";
+    let run = execute("ascii", program, &["--ascii"], "hi\nyo\n");
+    assert_eq!((run.code, run.stdout.as_str()), (0, "h\ny\n1000\n"));
+}
+
+#[test]
+fn out_of_fuel() {
+    let run = execute("fuel", "1105,1,0", &["--fuel", "10"], "");
+    assert_eq!(run.code, 4, "{:?}", run);
+    assert_eq!(run.stderr, "out of fuel at address 0\n");
+
+    // Halting takes an instruction too
+    assert_eq!(execute("fuel", "104,1,99", &["--fuel", "1"], "").code, 4);
+    assert_eq!(execute("fuel", "104,1,99", &["--fuel", "2"], "").code, 0);
+}
+
+#[test]
+fn missing_input() {
+    let run = execute("input", "3,0,4,0,3,0,99", &[], "5\n");
+    assert_eq!((run.code, run.stdout.as_str()), (3, "5\n"));
+    assert_eq!(run.stderr, "the program wants more input at address 4\n");
+}
+
+#[test]
+fn faults() {
+    for &(program, message) in &[
+        (
+            "1105,1,100000000000000",
+            "jumped to address 100000000000000, which is out of bounds\n",
+        ),
+        (
+            "1105,1,-1",
+            "jumped to address -1, which is out of bounds\n",
+        ),
+        ("42", "invalid instruction 42 at address 0\n"),
+    ] {
+        let run = execute("faults", program, &[], "");
+        assert_eq!(run.code, 5, "{:?}", run);
+        assert_eq!(run.stderr, message);
+    }
+}
@@ -0,0 +1,62 @@
+use std::{env, fs, path::Path};
+
+use intcode::{transpile::transpile, Interpreter};
+
+/// Bake the given program into native code
+fn bake(name: &str, memory: &[i64]) {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join(format!("{}.rs", name)),
+        transpile(memory),
+    )
+    .unwrap();
+}
+
+/// Bake the given day's program, with the given changes made to its memory, into native code
+fn bake_day(name: &str, day: &str, patches: &[(usize, i64)]) {
+    let input = format!("../inputs/{}.txt", day);
+    println!("cargo:rerun-if-changed={}", input);
+
+    let mut memory = Interpreter::from_input(&fs::read_to_string(&input).unwrap()).memory;
+    for &(idx, value) in patches {
+        memory[idx] = value;
+    }
+
+    bake(name, &memory);
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    bake_day("day05", "day05", &[]);
+    bake_day("day09", "day09", &[]);
+    bake_day("day13", "day13", &[]);
+
+    // Inserting two quarters lets the game be played
+    bake_day("day13_free_play", "day13", &[(0, 2)]);
+
+    // Turns its own addition into a multiplication through a relative address, which the
+    // transpiler can't see coming
+    bake(
+        "self_modifying",
+        &[
+            109, 9, // arb 9
+            21101, 1102, 0, 0, // add 1102, 0 -> [rb]
+            1105, 1, 9, // jnz 1, 9
+            1101, 3, 4, 17, // add 3, 4 -> [17]
+            4, 17, // out [17]
+            99, 0, 0,
+        ],
+    );
+
+    // Has an extension opcode store over the parameter of an instruction baked before it
+    bake(
+        "extension_store",
+        &[
+            104, 1, // out 1
+            50, 1, // ext -> [1]
+            6, 9, 10, // jz [9], [10]
+            0, 0, 0, 0,
+        ],
+    );
+}
@@ -0,0 +1,32 @@
+use std::{env, fs, process};
+
+use intcode::{transpile::transpile, Interpreter};
+
+fn main() {
+    let args: Vec<_> = env::args().skip(1).collect();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("usage: intcode-transpile <program> [output]");
+        process::exit(2);
+    }
+
+    let program = fs::read_to_string(&args[0]).unwrap_or_else(|err| {
+        eprintln!("could not read {}: {}", args[0], err);
+        process::exit(1);
+    });
+
+    let interpreter = Interpreter::parse(&program).unwrap_or_else(|err| {
+        eprintln!("could not parse {}: {}", args[0], err);
+        process::exit(1);
+    });
+
+    let source = transpile(&interpreter.memory);
+
+    match args.get(1) {
+        Some(output) => fs::write(output, source).unwrap_or_else(|err| {
+            eprintln!("could not write {}: {}", output, err);
+            process::exit(1);
+        }),
+
+        None => print!("{}", source),
+    }
+}
@@ -0,0 +1,22 @@
+use std::{fs, path::Path, process::Command};
+
+#[test]
+fn malformed_program() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("intcode-transpile-malformed.txt");
+    fs::write(&path, "1,2,x,4\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_intcode-transpile"))
+        .arg(&path)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        format!(
+            "could not parse {}: value 2 of the program, \"x\", isn't an integer\n",
+            path.display()
+        )
+    );
+}
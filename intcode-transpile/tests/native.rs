@@ -0,0 +1,174 @@
+use intcode::{
+    testing::{breakout, store_then_halt, Machine},
+    Interpreter,
+};
+
+macro_rules! baked {
+    ($($name:ident),*) => {
+        $(
+            #[allow(clippy::all, dead_code)]
+            mod $name {
+                include!(concat!(env!("OUT_DIR"), "/", stringify!($name), ".rs"));
+            }
+        )*
+    };
+}
+
+baked!(
+    day05,
+    day09,
+    day13,
+    day13_free_play,
+    self_modifying,
+    extension_store
+);
+
+macro_rules! machine {
+    ($($name:ident),*) => {
+        $(
+            impl Machine for $name::Program {
+                fn resume(&mut self) {
+                    self.run();
+                }
+
+                fn interpreter(&mut self) -> &mut Interpreter {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+machine!(day13, day13_free_play);
+
+#[test]
+fn diagnostics_match_interpreter() {
+    for &system in &[1, 5] {
+        let mut expected = Interpreter::new(day05::IMAGE.to_vec());
+        expected.input.push_back(system);
+        expected.resume();
+
+        let mut program = day05::Program::new();
+        program.input.push_back(system);
+        program.run();
+
+        // The diagnostic program patches one of its opcodes before anything else, so this mostly
+        // exercises handing instructions over to the interpreter
+        assert!(program.done);
+        assert_eq!(program.output, expected.output);
+    }
+}
+
+#[test]
+fn boost_matches_interpreter() {
+    let mut memory = day09::IMAGE.to_vec();
+    memory.resize(1080, 0);
+
+    for &mode in &[1, 2] {
+        let mut expected = Interpreter::new(memory.clone());
+        expected.input.push_back(mode);
+        expected.resume();
+
+        let mut program = day09::Program::from_interpreter(Interpreter::new(memory.clone()));
+        program.input.push_back(mode);
+        program.run();
+
+        assert!(program.is_native());
+        assert_eq!(program.output, expected.output);
+        assert_eq!(program.into_interpreter().memory, expected.memory);
+    }
+}
+
+#[test]
+fn breakout_matches_interpreter() {
+    let mut memory = day13::IMAGE.to_vec();
+    memory.extend_from_slice(&[0; 14]);
+
+    let mut expected = Interpreter::new(memory.clone());
+    expected.resume();
+
+    let mut program = day13::Program::from_interpreter(Interpreter::new(memory.clone()));
+    program.run();
+
+    assert!(program.is_native());
+    assert_eq!(program.output, expected.output);
+
+    // The game can be played by inserting quarters into the image the module was baked from too
+    memory[0] = 2;
+    let mut expected = Interpreter::new(memory.clone());
+    let mut program = day13::Program::from_interpreter(Interpreter::new(memory));
+    assert_eq!(breakout(&mut program), breakout(&mut expected));
+}
+
+#[test]
+fn free_play_matches_interpreter() {
+    let mut memory = day13_free_play::IMAGE.to_vec();
+    memory.extend_from_slice(&[0; 14]);
+
+    let mut expected = Interpreter::new(memory.clone());
+    let mut program = day13_free_play::Program::from_interpreter(Interpreter::new(memory));
+    assert_eq!(breakout(&mut program), breakout(&mut expected));
+    assert!(program.is_native());
+}
+
+#[test]
+fn stepping_matches_interpreter() {
+    let mut memory = day09::IMAGE.to_vec();
+    memory.resize(1080, 0);
+
+    let mut expected = Interpreter::new(memory.clone());
+    expected.input.push_back(1);
+
+    let mut program = day09::Program::from_interpreter(Interpreter::new(memory));
+    program.input.push_back(1);
+
+    loop {
+        let stepped = program.step();
+        assert_eq!(stepped, expected.step());
+        assert_eq!(program.pc, expected.pc);
+        assert_eq!(program.relative_base, expected.relative_base);
+        assert_eq!(program.output, expected.output);
+
+        if !stepped {
+            break;
+        }
+    }
+}
+
+#[test]
+fn self_modification_falls_back() {
+    let mut program = self_modifying::Program::new();
+    assert!(program.is_native());
+
+    program.run();
+
+    assert!(!program.is_native());
+    assert!(program.done);
+    assert_eq!(program.output, [12]);
+
+    // Making the same change before handing the interpreter over is noticed too
+    let mut memory = self_modifying::IMAGE.to_vec();
+    memory[9] = 1102;
+    let mut program = self_modifying::Program::from_interpreter(Interpreter::new(memory));
+    assert!(!program.is_native());
+
+    program.run();
+    assert_eq!(program.output, [12]);
+}
+
+#[test]
+fn extension_stores_fall_back() {
+    let mut expected =
+        Interpreter::new(extension_store::IMAGE.to_vec()).with_extensions(vec![store_then_halt()]);
+    expected.run();
+    assert_eq!(expected.output, [1, 42]);
+
+    let interpreter =
+        Interpreter::new(extension_store::IMAGE.to_vec()).with_extensions(vec![store_then_halt()]);
+    let mut program = extension_store::Program::from_interpreter(interpreter);
+    assert!(program.is_native());
+
+    program.run();
+    assert!(!program.is_native());
+    assert_eq!(program.into_interpreter(), expected);
+}
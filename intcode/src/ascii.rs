@@ -0,0 +1,187 @@
+use alloc::{borrow::ToOwned, collections::VecDeque, string::String};
+use core::{error, fmt};
+
+use crate::Interpreter;
+
+/// Why talking to a program in ASCII failed. Reads which fail leave the output untouched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The output ran out before what was being read, leaving what there was of it
+    Exhausted(String),
+
+    /// The output has a value which isn't an ASCII character, usually the program's answer
+    NotAscii(i64),
+
+    /// The output has something other than the expected text
+    Unexpected { expected: String, found: String },
+
+    /// The program only produced text where an answer was expected
+    NoAnswer(String),
+
+    /// The host tried sending a character the program couldn't read
+    Unsendable(char),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Exhausted(text) => write!(f, "output ran out after {:?}", text),
+            Error::NotAscii(value) => write!(f, "{} isn't an ASCII character", value),
+            Error::Unexpected { expected, found } => {
+                write!(f, "expected {:?} but found {:?}", expected, found)
+            }
+            Error::NoAnswer(text) => write!(f, "expected an answer but got text:\n{}", text),
+            Error::Unsendable(c) => write!(f, "can't send {:?} as it isn't ASCII", c),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+fn is_ascii(value: i64) -> bool {
+    (0..128).contains(&value)
+}
+
+/// The text from the front of the output up to and including the given delimiter
+fn scan(output: &VecDeque<i64>, delimiter: &str) -> Result<String, Error> {
+    let mut text = String::new();
+    if delimiter.is_empty() {
+        return Ok(text);
+    }
+
+    for &value in output {
+        if !is_ascii(value) {
+            return Err(Error::NotAscii(value));
+        }
+
+        text.push(value as u8 as char);
+        if text.ends_with(delimiter) {
+            return Ok(text);
+        }
+    }
+
+    Err(Error::Exhausted(text))
+}
+
+/// Read a line from the front of the output, without its newline
+pub fn read_line(output: &mut VecDeque<i64>) -> Result<String, Error> {
+    read_until(output, "\n")
+}
+
+/// Read everything from the front of the output up to and including the prompt, returning what
+/// came before it
+pub fn read_until(output: &mut VecDeque<i64>, prompt: &str) -> Result<String, Error> {
+    let mut text = scan(output, prompt)?;
+    output.drain(..text.len());
+    text.truncate(text.len() - prompt.len());
+    Ok(text)
+}
+
+/// Read a line from the front of the output which must be exactly the given text
+pub fn expect(output: &mut VecDeque<i64>, expected: &str) -> Result<(), Error> {
+    let line = scan(output, "\n")?;
+    let found = &line[..line.len() - 1];
+    if found != expected {
+        return Err(Error::Unexpected {
+            expected: expected.to_owned(),
+            found: found.to_owned(),
+        });
+    }
+
+    output.drain(..line.len());
+    Ok(())
+}
+
+/// Queue the line, followed by a newline, as the program's input
+pub fn send_line(interpreter: &mut Interpreter, line: &str) -> Result<(), Error> {
+    if let Some(c) = line.chars().find(|c| !c.is_ascii()) {
+        return Err(Error::Unsendable(c));
+    }
+
+    interpreter.input_from_ascii(line);
+    interpreter.input.push_back(b'\n' as i64);
+    Ok(())
+}
+
+/// The answer programs give by ending their output with a value too big to be a character. If
+/// there isn't one, the error has the text the program printed instead, which usually explains
+/// what went wrong.
+pub fn answer(output: &VecDeque<i64>) -> Result<i64, Error> {
+    match output.back() {
+        Some(&value) if !is_ascii(value) => Ok(value),
+        _ => Err(Error::NoAnswer(
+            output.iter().map(|&value| value as u8 as char).collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn output(text: &str) -> VecDeque<i64> {
+        text.bytes().map(|c| c as i64).collect()
+    }
+
+    #[test]
+    fn reading() {
+        let mut out = output("Hello\nCommand?\nrest");
+        assert_eq!(expect(&mut out, "Hello"), Ok(()));
+        assert_eq!(read_until(&mut out, "?\n"), Ok("Command".to_owned()));
+        assert_eq!(out, output("rest"));
+    }
+
+    #[test]
+    fn errors() {
+        let mut out = output("Hello\npartial");
+        assert_eq!(
+            expect(&mut out, "Goodbye"),
+            Err(Error::Unexpected {
+                expected: "Goodbye".to_owned(),
+                found: "Hello".to_owned(),
+            })
+        );
+        assert_eq!(read_line(&mut out), Ok("Hello".to_owned()));
+        assert_eq!(
+            read_line(&mut out),
+            Err(Error::Exhausted("partial".to_owned()))
+        );
+        assert_eq!(out, output("partial"));
+
+        // Values either side of the characters aren't text, and reading stops at them
+        for &value in &[-1, 128, 19_690_720] {
+            let mut out = output("ab");
+            out.push_back(value);
+            out.push_back(b'\n' as i64);
+            assert_eq!(read_line(&mut out), Err(Error::NotAscii(value)));
+            assert_eq!(out.len(), 4);
+        }
+
+        assert_eq!(
+            answer(&output("ok\n")),
+            Err(Error::NoAnswer("ok\n".to_owned()))
+        );
+        assert_eq!(
+            answer(&VecDeque::new()),
+            Err(Error::NoAnswer(String::new()))
+        );
+        let mut out = output("done\n");
+        out.push_back(1234);
+        assert_eq!(answer(&out), Ok(1234));
+    }
+
+    #[test]
+    fn sending() {
+        let mut interpreter = Interpreter::new(vec![99]);
+        interpreter.input_from_ascii("AB");
+        assert_eq!(send_line(&mut interpreter, "n 1"), Ok(()));
+        assert_eq!(interpreter.input, [65, 66, 110, 32, 49, 10]);
+
+        assert_eq!(
+            send_line(&mut interpreter, "caf\u{e9}"),
+            Err(Error::Unsendable('\u{e9}'))
+        );
+        assert_eq!(interpreter.input.len(), 6);
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use crate::Interpreter;
+
+/// The stack size of each worker thread, the same as the main thread's as `Interpreter::run` can
+/// recurse deeply in debug builds
+const STACK_SIZE: usize = 8 << 20;
+
+/// Hand each job a fresh copy of `program` to set up and run however it likes, spreading the jobs
+/// across a pool of threads, and return their results in the same order as the jobs
+pub fn run<I, J, R>(program: &Interpreter, jobs: I) -> Vec<R>
+where
+    I: IntoIterator<Item = J>,
+    I::IntoIter: Send,
+    J: FnOnce(Interpreter) -> R + Send,
+    R: Send,
+{
+    run_until(program, jobs, |_| false)
+}
+
+/// Like `run`, but stop once a job's result satisfies `stop`. The results are those of every job
+/// up to and including the first one in order that satisfied it, and no jobs after that one are
+/// started once it's been found.
+pub fn run_until<I, J, R>(
+    program: &Interpreter,
+    jobs: I,
+    stop: impl Fn(&R) -> bool + Sync,
+) -> Vec<R>
+where
+    I: IntoIterator<Item = J>,
+    I::IntoIter: Send,
+    J: FnOnce(Interpreter) -> R + Send,
+    R: Send,
+{
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+    // Jobs are started in order, so by the time every thread is done every job before the first
+    // one to satisfy `stop` must have finished
+    let jobs = Mutex::new(jobs.into_iter().enumerate());
+    let first = AtomicUsize::new(usize::MAX);
+    let results = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, || loop {
+                    let (idx, job) = match jobs.lock().unwrap().next() {
+                        Some((idx, _)) if idx > first.load(Ordering::Relaxed) => break,
+                        Some(next) => next,
+                        None => break,
+                    };
+
+                    let result = job(program.clone());
+                    if stop(&result) {
+                        first.fetch_min(idx, Ordering::Relaxed);
+                    }
+                    results.lock().unwrap().push((idx, result));
+                })
+                .unwrap();
+        }
+    });
+
+    let first = first.into_inner();
+    let mut results = results.into_inner().unwrap();
+    results.sort_unstable_by_key(|&(idx, _)| idx);
+    results
+        .into_iter()
+        .take_while(|&(idx, _)| idx <= first)
+        .map(|(_, result)| result)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Doubles its input
+    fn double() -> Interpreter {
+        Interpreter::new(vec![3, 0, 1002, 0, 2, 0, 4, 0, 99])
+    }
+
+    /// A job doubling `n` which takes longer the smaller `n` is, so later jobs tend to finish first
+    fn job(n: i64) -> impl FnOnce(Interpreter) -> i64 {
+        move |mut interpreter| {
+            thread::sleep(Duration::from_millis(5 * (10 - n as u64 % 10)));
+            interpreter.input.push_back(n);
+            interpreter.run();
+            interpreter.output[0]
+        }
+    }
+
+    #[test]
+    fn results_in_order() {
+        let results = run(&double(), (0..20).map(job));
+        assert_eq!(results, (0..20).map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stops_early() {
+        let started = AtomicUsize::new(0);
+        let jobs = (0..1000).map(|n| {
+            let started = &started;
+            move |interpreter| {
+                started.fetch_add(1, Ordering::Relaxed);
+                job(n)(interpreter)
+            }
+        });
+
+        let results = run_until(&double(), jobs, |&result| result >= 10);
+        assert_eq!(results, [0, 2, 4, 6, 8, 10]);
+
+        // Other threads may get through a few more jobs while the one which stops them runs, but
+        // nowhere near all of them
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        assert!(started.into_inner() <= 10 * threads);
+    }
+}
@@ -0,0 +1,645 @@
+use alloc::vec::Vec;
+use core::{cell::Cell, fmt};
+
+use crate::{
+    decode::{Instruction, Mode, Op},
+    Interpreter, Watched,
+};
+
+/// Marks an address at which no block has been compiled yet
+const NO_BLOCK: u32 = u32::MAX;
+
+/// How many blocks may be chained into each other before control returns to `BlockCache::run`.
+/// In release mode the chaining is done with tail calls, but this keeps the stack bounded even
+/// when it isn't.
+const CHAIN: u32 = 256;
+
+/// How many jumps with a constant condition and target a single block may be compiled through
+const MAX_JUMPS: u32 = 16;
+
+// Each parameter mode gets its own type so that every combination of modes compiles down to a
+// specialised function, in the same spirit as the jump table's per-mode entries. All the unsafes
+// are to avoid bounds checking in release mode, as in the jump table.
+
+trait Load {
+    /// Whether the loaded value is the same every time the step runs
+    const CONSTANT: bool = false;
+
+    fn load(interpreter: &Interpreter, arg: i64) -> i64;
+}
+
+trait Store {
+    fn address(interpreter: &Interpreter, arg: i64) -> usize;
+}
+
+struct Immediate;
+struct Absolute;
+struct Relative;
+
+/// A parameter the program has been seen overwriting, which is read from memory every time the
+/// instruction executes. Its argument packs the parameter's address together with its mode.
+struct Patched;
+
+fn pack(mode: Mode, idx: usize) -> i64 {
+    (idx as i64) << 2
+        | match mode {
+            Mode::Position => 0,
+            Mode::Immediate => 1,
+            Mode::Relative => 2,
+        }
+}
+
+#[inline(always)]
+fn read(interpreter: &Interpreter, idx: usize) -> i64 {
+    debug_assert!(idx < interpreter.memory.len());
+    unsafe { *interpreter.memory.get_unchecked(idx) }
+}
+
+impl Load for Immediate {
+    const CONSTANT: bool = true;
+
+    #[inline(always)]
+    fn load(_: &Interpreter, arg: i64) -> i64 {
+        arg
+    }
+}
+
+impl Load for Absolute {
+    #[inline(always)]
+    fn load(interpreter: &Interpreter, arg: i64) -> i64 {
+        read(interpreter, arg as usize)
+    }
+}
+
+impl Load for Relative {
+    #[inline(always)]
+    fn load(interpreter: &Interpreter, arg: i64) -> i64 {
+        read(interpreter, Self::address(interpreter, arg))
+    }
+}
+
+impl Load for Patched {
+    fn load(interpreter: &Interpreter, arg: i64) -> i64 {
+        let value = read(interpreter, (arg >> 2) as usize);
+        match arg & 3 {
+            0 => Absolute::load(interpreter, value),
+            1 => value,
+            _ => Relative::load(interpreter, value),
+        }
+    }
+}
+
+impl Store for Absolute {
+    #[inline(always)]
+    fn address(_: &Interpreter, arg: i64) -> usize {
+        arg as usize
+    }
+}
+
+impl Store for Relative {
+    #[inline(always)]
+    fn address(interpreter: &Interpreter, arg: i64) -> usize {
+        (interpreter.relative_base + arg) as usize
+    }
+}
+
+impl Store for Patched {
+    fn address(interpreter: &Interpreter, arg: i64) -> usize {
+        let value = read(interpreter, (arg >> 2) as usize);
+        match arg & 3 {
+            0 => Absolute::address(interpreter, value),
+            _ => Relative::address(interpreter, value),
+        }
+    }
+}
+
+trait Arithmetic {
+    fn apply(a: i64, b: i64) -> i64;
+}
+
+struct Add;
+struct Mul;
+struct LessThan;
+struct Equals;
+
+impl Arithmetic for Add {
+    #[inline(always)]
+    fn apply(a: i64, b: i64) -> i64 {
+        a + b
+    }
+}
+
+impl Arithmetic for Mul {
+    #[inline(always)]
+    fn apply(a: i64, b: i64) -> i64 {
+        a * b
+    }
+}
+
+impl Arithmetic for LessThan {
+    #[inline(always)]
+    fn apply(a: i64, b: i64) -> i64 {
+        (a < b) as i64
+    }
+}
+
+impl Arithmetic for Equals {
+    #[inline(always)]
+    fn apply(a: i64, b: i64) -> i64 {
+        (a == b) as i64
+    }
+}
+
+/// What `BlockCache::run` should do once compiled code hands control back to it
+#[derive(Clone, Copy)]
+enum Outcome {
+    Continue(usize),
+
+    /// The program wrote into compiled code at the first address, continue from the second
+    Invalidate(usize, usize),
+
+    Pause,
+}
+
+/// Everything compiled code can see besides the interpreter
+struct Context<'a> {
+    steps: &'a [Step],
+    entries: &'a [u32],
+    code: &'a [bool],
+
+    /// Set by the step which hands control back
+    outcome: Cell<Outcome>,
+}
+
+/// Execute the given step, along with every step after it, chaining into at most the given number
+/// of further blocks
+type Run = fn(&mut Interpreter, &Context, &Step, u32);
+
+/// A single compiled instruction. Just like the jump table's entries, every step finishes by
+/// calling the next one so that each has its own well-predicted indirect branch.
+#[derive(Clone)]
+struct Step {
+    run: Run,
+    args: [i64; 3],
+
+    /// The address of the following instruction
+    next: usize,
+
+    /// How far the relative base has been moved by `arb` instructions folded into this block. The
+    /// last step of a block applies it before doing anything else, and every other step only when
+    /// it leaves the block early.
+    base: i64,
+
+    /// For the last step of a block, the index of the first step of the block it continues at
+    /// when its jump is taken and when it isn't, once they're known. The first is only used if
+    /// the jump's target is constant.
+    links: [Cell<u32>; 2],
+}
+
+fn unlinked() -> [Cell<u32>; 2] {
+    [Cell::new(NO_BLOCK), Cell::new(NO_BLOCK)]
+}
+
+/// Continue with the step after the given one in the same block
+#[inline(always)]
+fn proceed(interpreter: &mut Interpreter, context: &Context, step: &Step, chain: u32) {
+    // Every block ends in a step which doesn't proceed, so this is always in bounds
+    let step = unsafe { &*(step as *const Step).add(1) };
+    (step.run)(interpreter, context, step, chain)
+}
+
+/// Continue from the given address, chaining straight into its block if it's been compiled
+#[inline(always)]
+fn jump(interpreter: &mut Interpreter, context: &Context, pc: usize, chain: u32) {
+    match context.entries.get(pc) {
+        Some(&idx) if idx != NO_BLOCK && chain != 0 => {
+            debug_assert!((idx as usize) < context.steps.len());
+            let step = unsafe { context.steps.get_unchecked(idx as usize) };
+            (step.run)(interpreter, context, step, chain - 1)
+        }
+
+        _ => context.outcome.set(Outcome::Continue(pc)),
+    }
+}
+
+/// Continue from an address which is always the same for the given link, remembering where its
+/// block's steps are to skip looking it up next time
+#[inline(always)]
+fn jump_linked(
+    interpreter: &mut Interpreter,
+    context: &Context,
+    link: &Cell<u32>,
+    pc: usize,
+    chain: u32,
+) {
+    let idx = link.get();
+    if idx != NO_BLOCK && chain != 0 {
+        debug_assert!((idx as usize) < context.steps.len());
+        let step = unsafe { context.steps.get_unchecked(idx as usize) };
+        return (step.run)(interpreter, context, step, chain - 1);
+    }
+
+    if let Some(&idx) = context.entries.get(pc) {
+        link.set(idx);
+    }
+    jump(interpreter, context, pc, chain)
+}
+
+/// Store a value, leaving the block if it overwrote compiled code
+macro_rules! store {
+    ($interpreter:ident, $context:ident, $idx:expr, $value:expr, $next:expr, $base:expr) => {{
+        let idx = $idx;
+        debug_assert!(idx < $interpreter.memory.len());
+        unsafe {
+            *$interpreter.memory.get_unchecked_mut(idx) = $value;
+
+            // The rest of this block, or any other, might have just been overwritten
+            if *$context.code.get_unchecked(idx) {
+                $interpreter.relative_base += $base;
+                $context.outcome.set(Outcome::Invalidate(idx, $next));
+                return;
+            }
+        }
+    }};
+}
+
+fn triadic<F: Arithmetic, A: Load, B: Load, C: Store>(
+    interpreter: &mut Interpreter,
+    context: &Context,
+    step: &Step,
+    chain: u32,
+) {
+    let value = F::apply(
+        A::load(interpreter, step.args[0]),
+        B::load(interpreter, step.args[1]),
+    );
+    store!(
+        interpreter,
+        context,
+        C::address(interpreter, step.args[2]),
+        value,
+        step.next,
+        step.base
+    );
+    proceed(interpreter, context, step, chain)
+}
+
+fn output<A: Load>(interpreter: &mut Interpreter, context: &Context, step: &Step, chain: u32) {
+    let value = A::load(interpreter, step.args[0]);
+    interpreter.output.push_back(value);
+    proceed(interpreter, context, step, chain)
+}
+
+fn adjust_base<A: Load>(interpreter: &mut Interpreter, context: &Context, step: &Step, chain: u32) {
+    interpreter.relative_base += A::load(interpreter, step.args[0]);
+    proceed(interpreter, context, step, chain)
+}
+
+fn jump_if_true<A: Load, B: Load>(
+    interpreter: &mut Interpreter,
+    context: &Context,
+    step: &Step,
+    chain: u32,
+) {
+    interpreter.relative_base += step.base;
+    if A::load(interpreter, step.args[0]) != 0 {
+        let pc = B::load(interpreter, step.args[1]) as usize;
+        if B::CONSTANT {
+            jump_linked(interpreter, context, &step.links[0], pc, chain)
+        } else {
+            jump(interpreter, context, pc, chain)
+        }
+    } else {
+        jump_linked(interpreter, context, &step.links[1], step.next, chain)
+    }
+}
+
+fn jump_if_false<A: Load, B: Load>(
+    interpreter: &mut Interpreter,
+    context: &Context,
+    step: &Step,
+    chain: u32,
+) {
+    interpreter.relative_base += step.base;
+    if A::load(interpreter, step.args[0]) == 0 {
+        let pc = B::load(interpreter, step.args[1]) as usize;
+        if B::CONSTANT {
+            jump_linked(interpreter, context, &step.links[0], pc, chain)
+        } else {
+            jump(interpreter, context, pc, chain)
+        }
+    } else {
+        jump_linked(interpreter, context, &step.links[1], step.next, chain)
+    }
+}
+
+fn input<A: Store>(interpreter: &mut Interpreter, context: &Context, step: &Step, chain: u32) {
+    interpreter.relative_base += step.base;
+    match interpreter.input.pop_front() {
+        Some(value) => {
+            store!(
+                interpreter,
+                context,
+                A::address(interpreter, step.args[0]),
+                value,
+                step.next,
+                0
+            );
+            jump_linked(interpreter, context, &step.links[1], step.next, chain)
+        }
+
+        None => {
+            interpreter.pc = step.next - 2;
+            context.outcome.set(Outcome::Pause);
+        }
+    }
+}
+
+fn halt(interpreter: &mut Interpreter, context: &Context, step: &Step, _: u32) {
+    interpreter.relative_base += step.base;
+    interpreter.pc = step.next - 1;
+    interpreter.done = true;
+    context.outcome.set(Outcome::Pause);
+}
+
+/// Run an instruction that couldn't be compiled, which is either an extension opcode or invalid,
+/// on the interpreter, leaving the block if it stored into compiled code
+fn invalid(interpreter: &mut Interpreter, context: &Context, step: &Step, _: u32) {
+    interpreter.relative_base += step.base;
+    interpreter.pc = step.next;
+
+    let mut memory = Watched::new(context.code);
+    let executed = interpreter.step_on(&mut memory);
+    context.outcome.set(match memory.overwrote {
+        Some(idx) if executed => Outcome::Invalidate(idx, interpreter.pc),
+        _ if executed => Outcome::Continue(interpreter.pc),
+        _ => Outcome::Pause,
+    });
+}
+
+/// Pick the instantiation of `$f` matching the mode of each parameter, given as a pair of its
+/// mode and whether it's been patched
+macro_rules! specialise {
+    // Loads may use any mode
+    (@load $f:ident [$($ty:ty),*] ($param:expr, $($rest:tt)*)) => {
+        match $param {
+            (Mode::Position, false) => specialise!(@next $f [$($ty,)* Absolute] ($($rest)*)),
+            (Mode::Immediate, false) => specialise!(@next $f [$($ty,)* Immediate] ($($rest)*)),
+            (Mode::Relative, false) => specialise!(@next $f [$($ty,)* Relative] ($($rest)*)),
+            (_, true) => specialise!(@next $f [$($ty,)* Patched] ($($rest)*)),
+        }
+    };
+
+    // Stores may not be immediate
+    (@store $f:ident [$($ty:ty),*] ($param:expr, $($rest:tt)*)) => {
+        match $param {
+            (Mode::Position, false) => specialise!(@next $f [$($ty,)* Absolute] ($($rest)*)),
+            (Mode::Relative, false) => specialise!(@next $f [$($ty,)* Relative] ($($rest)*)),
+            (_, true) => specialise!(@next $f [$($ty,)* Patched] ($($rest)*)),
+            (Mode::Immediate, false) => {
+                unreachable!("decoder accepted an immediate output parameter")
+            }
+        }
+    };
+
+    (@next $f:ident [$($ty:ty),*] ()) => { $f::<$($ty),*> as Run };
+    (@next $f:ident [$($ty:ty),*] (load $($rest:tt)*)) => {
+        specialise!(@load $f [$($ty),*] ($($rest)*))
+    };
+    (@next $f:ident [$($ty:ty),*] (store $($rest:tt)*)) => {
+        specialise!(@store $f [$($ty),*] ($($rest)*))
+    };
+
+    ($f:ident [$($ty:ty),*] $($rest:tt)*) => { specialise!(@next $f [$($ty),*] ($($rest)*)) };
+}
+
+/// An alternative execution engine which decodes each basic block of a program once, compiles it
+/// into a sequence of functions specialised for the instructions' parameter modes and then
+/// replays those every time execution reaches the block again.
+///
+/// The cache tracks which addresses have been baked into compiled blocks and throws every block
+/// away when the program writes into one of them. Parameters which have been overwritten once are
+/// read from memory from then on, so the common intcode idiom of patching an instruction's
+/// address before executing it only costs a single recompilation. Writes made by the host directly
+/// into `Interpreter::memory` are not seen, so after patching code between runs `invalidate` must
+/// be called.
+///
+/// While compiling, `arb` instructions with an immediate parameter are folded into the relative
+/// parameters of the steps after them and jumps whose condition and target are both immediate are
+/// followed, so neither costs anything at runtime.
+#[derive(Clone, Default)]
+pub struct BlockCache {
+    /// Every compiled block's steps, one after the other
+    steps: Vec<Step>,
+
+    /// Maps every address to the index of the first step of the block starting there, if any
+    entries: Vec<u32>,
+
+    /// Whether each address holds a value baked into a compiled block
+    code: Vec<bool>,
+
+    /// Whether the program has ever overwritten each address while it was compiled code
+    patched: Vec<bool>,
+}
+
+impl fmt::Debug for BlockCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BlockCache")
+            .field("steps", &self.steps.len())
+            .field("patched", &self.patched.iter().filter(|&&p| p).count())
+            .finish()
+    }
+}
+
+impl BlockCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget every compiled block. Addresses the program has patched before stay patched, so
+    /// recompiled blocks read them from memory instead of being invalidated again.
+    pub fn invalidate(&mut self) {
+        self.steps.clear();
+        self.entries.iter_mut().for_each(|entry| *entry = NO_BLOCK);
+        self.code.iter_mut().for_each(|code| *code = false);
+    }
+
+    /// Compile the block starting at `start` and return the index of its first step
+    fn compile(&mut self, memory: &[i64], start: usize) -> u32 {
+        let first = self.steps.len() as u32;
+        let mut pc = start;
+
+        // How far `arb` instructions folded into the block so far have moved the relative base
+        let mut base = 0;
+
+        // How many more constant jumps the block may still be extended through
+        let mut jumps = MAX_JUMPS;
+
+        loop {
+            let instruction = match Instruction::decode(memory, pc) {
+                Some(instruction) => instruction,
+                None => {
+                    // The program might still fix the instruction up before reaching it
+                    if let Some(code) = self.code.get_mut(pc) {
+                        *code = true;
+                    }
+
+                    self.steps.push(Step {
+                        run: invalid,
+                        args: [0; 3],
+                        next: pc,
+                        base,
+                        links: unlinked(),
+                    });
+                    break;
+                }
+            };
+
+            let at = pc;
+            pc += instruction.size();
+
+            // Opcodes are always baked in, but parameters only if they've never been patched
+            self.code[at] = true;
+            for idx in at + 1..pc {
+                self.code[idx] = !self.patched[idx];
+            }
+
+            let patched = &self.patched[at + 1..pc];
+            let constant = |i: usize| {
+                let param = instruction.params()[i];
+                if param.mode == Mode::Immediate && !patched[i] {
+                    Some(param.value)
+                } else {
+                    None
+                }
+            };
+
+            // Moving the relative base by a constant costs nothing if later steps account for it,
+            // and neither does a jump whose condition and target are known ahead of time
+            match instruction.op {
+                Op::AdjustBase => {
+                    if let Some(offset) = constant(0) {
+                        base += offset;
+                        continue;
+                    }
+                }
+
+                Op::JumpIfTrue | Op::JumpIfFalse if jumps != 0 => {
+                    if let (Some(condition), Some(target)) = (constant(0), constant(1)) {
+                        if (condition != 0) == (instruction.op == Op::JumpIfTrue) {
+                            pc = target as usize;
+                        }
+                        jumps -= 1;
+                        continue;
+                    }
+                }
+
+                _ => {}
+            }
+
+            let terminal = matches!(
+                instruction.op,
+                Op::JumpIfTrue | Op::JumpIfFalse | Op::Input | Op::Halt
+            );
+
+            // Patched parameters are read through the live relative base, so it has to be brought
+            // up to date before any step using them
+            if base != 0 && !terminal && patched.contains(&true) {
+                self.steps.push(Step {
+                    run: adjust_base::<Immediate>,
+                    args: [base, 0, 0],
+                    next: at,
+                    base: 0,
+                    links: unlinked(),
+                });
+                base = 0;
+            }
+
+            // Patched parameters are passed by address, everything else by value. The last step
+            // of a block applies the pending relative base offset itself, so only the others need
+            // it folded into their relative parameters.
+            let mut args = [0; 3];
+            let mut params = [(Mode::Position, false); 3];
+            for (i, param) in instruction.params().iter().enumerate() {
+                args[i] = if patched[i] {
+                    pack(param.mode, at + 1 + i)
+                } else if param.mode == Mode::Relative && !terminal {
+                    param.value + base
+                } else {
+                    param.value
+                };
+                params[i] = (param.mode, patched[i]);
+            }
+            let [a, b, c] = params;
+
+            let run = match instruction.op {
+                Op::Add => specialise!(triadic [Add] load a, load b, store c,),
+                Op::Mul => specialise!(triadic [Mul] load a, load b, store c,),
+                Op::LessThan => specialise!(triadic [LessThan] load a, load b, store c,),
+                Op::Equals => specialise!(triadic [Equals] load a, load b, store c,),
+                Op::Output => specialise!(output [] load a,),
+                Op::AdjustBase => specialise!(adjust_base [] load a,),
+                Op::JumpIfTrue => specialise!(jump_if_true [] load a, load b,),
+                Op::JumpIfFalse => specialise!(jump_if_false [] load a, load b,),
+                Op::Input => specialise!(input [] store a,),
+                Op::Halt => halt,
+            };
+
+            self.steps.push(Step {
+                run,
+                args,
+                next: pc,
+                base,
+                links: unlinked(),
+            });
+
+            if terminal {
+                break;
+            }
+        }
+
+        self.entries[start] = first;
+        first
+    }
+
+    /// Run the interpreter until it halts or needs more input, exactly like `Interpreter::run`
+    pub fn run(&mut self, interpreter: &mut Interpreter) {
+        // The host may have grown memory since the last run
+        if self.entries.len() < interpreter.memory.len() {
+            self.entries.resize(interpreter.memory.len(), NO_BLOCK);
+            self.code.resize(interpreter.memory.len(), false);
+            self.patched.resize(interpreter.memory.len(), false);
+        }
+
+        let mut pc = interpreter.pc;
+
+        loop {
+            let idx = match self.entries[pc] {
+                NO_BLOCK => self.compile(&interpreter.memory, pc),
+                idx => idx,
+            } as usize;
+
+            let context = Context {
+                steps: &self.steps,
+                entries: &self.entries,
+                code: &self.code,
+                outcome: Cell::new(Outcome::Pause),
+            };
+
+            let step = &self.steps[idx];
+            (step.run)(interpreter, &context, step, CHAIN);
+
+            match context.outcome.get() {
+                Outcome::Continue(next) => pc = next,
+
+                Outcome::Invalidate(patched, next) => {
+                    self.patched[patched] = true;
+                    self.invalidate();
+                    pc = next;
+                }
+
+                Outcome::Pause => return,
+            }
+        }
+    }
+}
@@ -0,0 +1,463 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::fmt;
+
+use crate::{
+    decode::{Instruction, Mode, Op},
+    Interpreter, Memory,
+};
+
+/// How a single memory cell was used while running a program
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Whether the cell was part of an executed instruction
+    pub executed: bool,
+
+    pub read: bool,
+    pub written: bool,
+}
+
+impl Usage {
+    pub fn is_untouched(self) -> bool {
+        !(self.executed || self.read || self.written)
+    }
+}
+
+/// Records which cells a program executes as instructions, which it reads or writes as data and
+/// which it never touches at all
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    /// The memory the program started with
+    image: Vec<i64>,
+
+    usage: Vec<Usage>,
+
+    /// Every instruction executed, by address, as it was when last executed there
+    instructions: BTreeMap<usize, Instruction>,
+}
+
+/// How many cells of a program were used in each way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub cells: usize,
+    pub executed: usize,
+
+    /// Cells read or written without being executed
+    pub data: usize,
+
+    pub untouched: usize,
+}
+
+/// Plain memory which remembers every cell an extension opcode's parameters load from and store
+/// into, as those aren't known until its handler has run
+#[derive(Default)]
+struct Recorded {
+    reads: Vec<usize>,
+    writes: Vec<usize>,
+}
+
+impl Memory for Recorded {
+    fn read(&mut self, memory: &[i64], idx: usize) -> i64 {
+        self.reads.push(idx);
+        memory[idx]
+    }
+
+    fn write(&mut self, memory: &mut [i64], idx: usize, value: i64) {
+        self.writes.push(idx);
+        memory[idx] = value;
+    }
+}
+
+impl Coverage {
+    pub fn new(interpreter: &Interpreter) -> Self {
+        Self {
+            image: interpreter.memory.clone(),
+            usage: vec![Usage::default(); interpreter.memory.len()],
+            instructions: BTreeMap::new(),
+        }
+    }
+
+    /// How each cell has been used so far, by address
+    pub fn usage(&self) -> &[Usage] {
+        &self.usage
+    }
+
+    fn mark(&mut self, idx: usize) -> &mut Usage {
+        if idx >= self.usage.len() {
+            self.usage.resize(idx + 1, Usage::default());
+        }
+        &mut self.usage[idx]
+    }
+
+    /// Execute a single instruction like `Interpreter::step` does, recording which cells it used
+    pub fn step(&mut self, interpreter: &mut Interpreter) -> bool {
+        let pc = interpreter.pc;
+        let instruction = match Instruction::decode(&interpreter.memory, pc) {
+            Some(instruction) => instruction,
+            None => return self.extension(interpreter),
+        };
+
+        // Work out which cells the instruction uses before running it, as running it may change
+        // the relative base. Jump targets are only read when the jump is taken.
+        let params = instruction.params();
+        let taken = match instruction.op {
+            Op::JumpIfTrue => interpreter.load(params[0]) != 0,
+            Op::JumpIfFalse => interpreter.load(params[0]) == 0,
+            _ => true,
+        };
+        let mut reads = Vec::with_capacity(params.len());
+        let mut write = None;
+        for (i, &param) in params.iter().enumerate() {
+            if param.mode == Mode::Immediate || (i == 1 && !taken) {
+                continue;
+            }
+
+            let idx = interpreter.address(param);
+            if instruction.op.output_parameter() == Some(i) {
+                write = Some(idx);
+            } else {
+                reads.push(idx);
+            }
+        }
+
+        let stepped = interpreter.step();
+
+        // A halt counts as executed, whereas an input with nothing to read hasn't happened yet
+        if stepped || interpreter.done {
+            for idx in pc..pc + instruction.size() {
+                self.mark(idx).executed = true;
+            }
+            for idx in reads {
+                self.mark(idx).read = true;
+            }
+            if let Some(idx) = write {
+                self.mark(idx).written = true;
+            }
+            self.instructions.insert(pc, instruction);
+        }
+
+        stepped
+    }
+
+    /// Execute the extension opcode at the program counter, recording which cells it used. Unlike
+    /// an input, an extension opcode which pauses or halts the program has still run.
+    fn extension(&mut self, interpreter: &mut Interpreter) -> bool {
+        let pc = interpreter.pc;
+        let size = match interpreter.extension_size() {
+            Some(size) => size,
+            None => return interpreter.step(),
+        };
+
+        let mut memory = Recorded::default();
+        let stepped = interpreter.step_on(&mut memory);
+
+        for idx in pc..pc + size {
+            self.mark(idx).executed = true;
+        }
+        for idx in memory.reads {
+            self.mark(idx).read = true;
+        }
+        for idx in memory.writes {
+            self.mark(idx).written = true;
+        }
+
+        stepped
+    }
+
+    /// Run the program until it halts or needs input, recording which cells it used
+    pub fn run(&mut self, interpreter: &mut Interpreter) {
+        while self.step(interpreter) {}
+    }
+
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary {
+            cells: self.usage.len(),
+            executed: 0,
+            data: 0,
+            untouched: 0,
+        };
+
+        for usage in &self.usage {
+            if usage.executed {
+                summary.executed += 1;
+            } else if usage.is_untouched() {
+                summary.untouched += 1;
+            } else {
+                summary.data += 1;
+            }
+        }
+
+        summary
+    }
+
+    /// An annotated disassembly of the program followed by its summary. Each line is marked with
+    /// `x` for executed instructions, `-` for instructions the program could have gone on to
+    /// execute but never did, `r` and `w` for cells read and written as data and `.` for cells
+    /// which were never touched.
+    pub fn report(&self) -> impl fmt::Display + '_ {
+        Report(self)
+    }
+
+    /// Where an executed instruction could have continued on to
+    fn successors(pc: usize, instruction: &Instruction) -> Vec<usize> {
+        let next = pc + instruction.size();
+        match instruction.op {
+            Op::Halt => vec![],
+
+            Op::JumpIfTrue | Op::JumpIfFalse => {
+                let [condition, target] = [instruction.params()[0], instruction.params()[1]];
+                let mut successors = Vec::with_capacity(2);
+                if target.mode == Mode::Immediate && target.value >= 0 {
+                    successors.push(target.value as usize);
+                }
+
+                let always = condition.mode == Mode::Immediate
+                    && (condition.value != 0) == (instruction.op == Op::JumpIfTrue);
+                if !always {
+                    successors.push(next);
+                }
+                successors
+            }
+
+            _ => vec![next],
+        }
+    }
+
+    /// Instructions which were never executed but which follow from executed ones, such as the
+    /// other side of a branch, along with everything that follows from those in turn. These are
+    /// the program's unexplored paths.
+    fn unreached(&self) -> BTreeMap<usize, Instruction> {
+        let mut unreached = BTreeMap::new();
+        let mut pending: Vec<_> = self
+            .instructions
+            .iter()
+            .flat_map(|(&pc, instruction)| Self::successors(pc, instruction))
+            .collect();
+
+        while let Some(pc) = pending.pop() {
+            if self.instructions.contains_key(&pc) || unreached.contains_key(&pc) {
+                continue;
+            }
+
+            let instruction = match Instruction::decode(&self.image, pc) {
+                Some(instruction) => instruction,
+                None => continue,
+            };
+
+            // Anything the program touched is either code we know about or data
+            let touched = self.usage[pc..]
+                .iter()
+                .take(instruction.size())
+                .any(|usage| !usage.is_untouched());
+            if touched {
+                continue;
+            }
+
+            unreached.insert(pc, instruction);
+            pending.extend(Self::successors(pc, &instruction));
+        }
+
+        unreached
+    }
+}
+
+struct Report<'a>(&'a Coverage);
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coverage = self.0;
+        let unreached = coverage.unreached();
+        let value = |idx: usize| coverage.image.get(idx).copied().unwrap_or(0);
+
+        let mut idx = 0;
+        while idx < coverage.usage.len() {
+            if let Some(instruction) = coverage.instructions.get(&idx) {
+                writeln!(f, "{:>6} x  {}", idx, instruction)?;
+                idx += instruction.size();
+                continue;
+            }
+
+            if let Some(instruction) = unreached.get(&idx) {
+                writeln!(f, "{:>6} -  {}", idx, instruction)?;
+                idx += instruction.size();
+                continue;
+            }
+
+            let usage = coverage.usage[idx];
+            let marker = match (usage.read, usage.written) {
+                (true, true) => "rw",
+                (true, false) => "r",
+                (false, true) => "w",
+                (false, false) if usage.executed => "x",
+                (false, false) => ".",
+            };
+
+            // Collapse runs of the same untouched value, such as zeroed scratch memory
+            let mut end = idx + 1;
+            if usage.is_untouched() {
+                while end < coverage.usage.len()
+                    && coverage.usage[end].is_untouched()
+                    && !unreached.contains_key(&end)
+                    && value(end) == value(idx)
+                {
+                    end += 1;
+                }
+            }
+
+            if end - idx > 2 {
+                writeln!(
+                    f,
+                    "{:>6} {:<2} {} (x{})",
+                    idx,
+                    marker,
+                    value(idx),
+                    end - idx
+                )?;
+            } else {
+                end = idx + 1;
+                writeln!(f, "{:>6} {:<2} {}", idx, marker, value(idx))?;
+            }
+
+            idx = end;
+        }
+
+        writeln!(f)?;
+        write!(f, "{}", coverage.summary())
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let percent = |n: usize| 100. * n as f64 / self.cells.max(1) as f64;
+        write!(
+            f,
+            "{} cells: {} executed ({:.1}%), {} data ({:.1}%), {} untouched ({:.1}%)",
+            self.cells,
+            self.executed,
+            percent(self.executed),
+            self.data,
+            percent(self.data),
+            self.untouched,
+            percent(self.untouched),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extension::{Effect, Extension};
+    use alloc::string::ToString;
+
+    #[test]
+    fn branch_never_taken() {
+        let mut interpreter = Interpreter::new(vec![
+            3, 11, // in -> [11]
+            1005, 11, 8, // jnz [11], 8
+            104, 0,  // out 0
+            99, //
+            4, 11, // out [11]
+            99, //
+            0, 0, 0, 0, 0,
+        ]);
+        interpreter.input.push_back(5);
+
+        let mut coverage = Coverage::new(&interpreter);
+        coverage.run(&mut interpreter);
+        assert_eq!(interpreter.output, [5]);
+
+        let executed: Vec<_> = (0..16)
+            .filter(|&idx| coverage.usage()[idx].executed)
+            .collect();
+        assert_eq!(executed, [0, 1, 2, 3, 4, 8, 9, 10]);
+        assert_eq!(
+            coverage.usage()[11],
+            Usage {
+                executed: false,
+                read: true,
+                written: true,
+            }
+        );
+        assert!(coverage.usage()[5..8]
+            .iter()
+            .all(|usage| usage.is_untouched()));
+
+        assert_eq!(
+            coverage.summary(),
+            Summary {
+                cells: 16,
+                executed: 8,
+                data: 1,
+                untouched: 7,
+            }
+        );
+
+        // The branch which wasn't taken shows up as instructions that were never executed
+        assert_eq!(
+            coverage.report().to_string(),
+            "     0 x  in [11]
+     2 x  jnz [11], 8
+     5 -  out 0
+     7 -  hlt
+     8 x  out [11]
+    10 x  hlt
+    11 rw 0
+    12 .  0 (x4)
+
+16 cells: 8 executed (50.0%), 1 data (6.2%), 7 untouched (43.8%)"
+        );
+    }
+
+    #[test]
+    fn extensions() {
+        let mut interpreter = Interpreter::new(vec![
+            50, 9, 10, // dbl [9] -> [10]
+            4, 10, // out [10]
+            99, //
+            0, 0, 0, //
+            7, 0,
+        ])
+        .with_extensions(vec![Extension::storing(50, 1, |_, args| {
+            Effect::Store(args[0] * 2)
+        })]);
+
+        let mut coverage = Coverage::new(&interpreter);
+        coverage.run(&mut interpreter);
+        assert_eq!(interpreter.output, [14]);
+
+        let executed: Vec<_> = (0..11)
+            .filter(|&idx| coverage.usage()[idx].executed)
+            .collect();
+        assert_eq!(executed, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            coverage.usage()[9],
+            Usage {
+                executed: false,
+                read: true,
+                written: false,
+            }
+        );
+        assert_eq!(
+            coverage.usage()[10],
+            Usage {
+                executed: false,
+                read: true,
+                written: true,
+            }
+        );
+
+        // The disassembler doesn't know the extension, so its cells are shown as plain values
+        assert_eq!(
+            coverage.report().to_string(),
+            "     0 x  50
+     1 x  9
+     2 x  10
+     3 x  out [10]
+     5 x  hlt
+     6 .  0 (x3)
+     9 r  7
+    10 rw 0
+
+11 cells: 6 executed (54.5%), 2 data (18.2%), 3 untouched (27.3%)"
+        );
+    }
+}
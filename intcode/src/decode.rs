@@ -0,0 +1,182 @@
+use core::fmt;
+
+/// How an instruction's parameter should be interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl Mode {
+    pub fn from_digit(digit: i64) -> Option<Self> {
+        match digit {
+            0 => Some(Mode::Position),
+            1 => Some(Mode::Immediate),
+            2 => Some(Mode::Relative),
+            _ => None,
+        }
+    }
+}
+
+/// The operation performed by an instruction, without its parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Mul,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustBase,
+    Halt,
+}
+
+impl Op {
+    pub fn from_code(code: i64) -> Option<Self> {
+        Some(match code {
+            1 => Op::Add,
+            2 => Op::Mul,
+            3 => Op::Input,
+            4 => Op::Output,
+            5 => Op::JumpIfTrue,
+            6 => Op::JumpIfFalse,
+            7 => Op::LessThan,
+            8 => Op::Equals,
+            9 => Op::AdjustBase,
+            99 => Op::Halt,
+            _ => return None,
+        })
+    }
+
+    pub fn code(self) -> i64 {
+        match self {
+            Op::Add => 1,
+            Op::Mul => 2,
+            Op::Input => 3,
+            Op::Output => 4,
+            Op::JumpIfTrue => 5,
+            Op::JumpIfFalse => 6,
+            Op::LessThan => 7,
+            Op::Equals => 8,
+            Op::AdjustBase => 9,
+            Op::Halt => 99,
+        }
+    }
+
+    /// How many parameters follow the opcode
+    pub fn arity(self) -> usize {
+        match self {
+            Op::Add | Op::Mul | Op::LessThan | Op::Equals => 3,
+            Op::JumpIfTrue | Op::JumpIfFalse => 2,
+            Op::Input | Op::Output | Op::AdjustBase => 1,
+            Op::Halt => 0,
+        }
+    }
+
+    /// Which parameter, if any, is an address the instruction stores into
+    pub fn output_parameter(self) -> Option<usize> {
+        match self {
+            Op::Add | Op::Mul | Op::LessThan | Op::Equals => Some(2),
+            Op::Input => Some(0),
+            _ => None,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Mul => "mul",
+            Op::Input => "in",
+            Op::Output => "out",
+            Op::JumpIfTrue => "jnz",
+            Op::JumpIfFalse => "jz",
+            Op::LessThan => "lt",
+            Op::Equals => "eq",
+            Op::AdjustBase => "arb",
+            Op::Halt => "hlt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Parameter {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+/// A fully decoded instruction. Only the first `op.arity()` parameters are meaningful.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub op: Op,
+    params: [Parameter; 3],
+}
+
+impl Instruction {
+    /// Decode the instruction starting at `pc`, returning `None` if the opcode or any of its modes
+    /// are invalid, if it stores into an immediate parameter or if it runs past the end of memory
+    pub fn decode(memory: &[i64], pc: usize) -> Option<Self> {
+        let word = *memory.get(pc)?;
+        if word < 0 {
+            return None;
+        }
+
+        let op = Op::from_code(word % 100)?;
+
+        let mut params = [Parameter {
+            mode: Mode::Position,
+            value: 0,
+        }; 3];
+
+        let mut modes = word / 100;
+        for (i, param) in params.iter_mut().enumerate().take(op.arity()) {
+            param.mode = Mode::from_digit(modes % 10)?;
+            param.value = *memory.get(pc + 1 + i)?;
+            modes /= 10;
+
+            if op.output_parameter() == Some(i) && param.mode == Mode::Immediate {
+                return None;
+            }
+        }
+
+        // Leftover mode digits have no entry in the interpreter's jump table, so they're invalid
+        if modes != 0 {
+            return None;
+        }
+
+        Some(Self { op, params })
+    }
+
+    pub fn params(&self) -> &[Parameter] {
+        &self.params[..self.op.arity()]
+    }
+
+    /// How many memory cells the instruction occupies
+    pub fn size(&self) -> usize {
+        1 + self.op.arity()
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "{}", self.value),
+            Mode::Relative if self.value < 0 => write!(f, "[rb{}]", self.value),
+            Mode::Relative => write!(f, "[rb+{}]", self.value),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.op.mnemonic())?;
+        for (i, param) in self.params().iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            param.fmt(f)?;
+        }
+        Ok(())
+    }
+}
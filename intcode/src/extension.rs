@@ -0,0 +1,326 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::{convert::TryFrom, fmt};
+
+use crate::{
+    decode::{Mode, Op, Parameter},
+    Interpreter, Memory,
+};
+
+/// What an extension opcode asks of the interpreter once its handler has run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Carry on with the next instruction
+    Continue,
+
+    /// Store the value into the opcode's output parameter and carry on
+    Store(i64),
+
+    /// Stop running as if waiting for input, except that the program counter is left after the
+    /// opcode so that running again carries on from there
+    Pause,
+
+    /// Halt the program as `99` would, recording the given exit code
+    Halt(i64),
+}
+
+type Handler = dyn Fn(&mut Interpreter, &[i64]) -> Effect + Send + Sync;
+
+/// An opcode on top of the standard ones, such as a host call or a debug print. Its parameters
+/// support the same modes as those of the standard opcodes, and its handler is given their values.
+///
+/// `BlockCache` and transpiled code notice `Effect::Store` overwriting their compiled code, but not
+/// a handler writing into `Interpreter::memory` directly, so handlers doing that to code must be run
+/// by the interpreter.
+#[derive(Clone)]
+pub struct Extension {
+    code: i64,
+    inputs: usize,
+    stores: bool,
+    handler: Arc<Handler>,
+}
+
+impl Extension {
+    /// An opcode with `inputs` parameters which are loaded and handed to `handler`
+    pub fn new(
+        code: i64,
+        inputs: usize,
+        handler: impl Fn(&mut Interpreter, &[i64]) -> Effect + Send + Sync + 'static,
+    ) -> Self {
+        assert!(
+            (0..100).contains(&code) && Op::from_code(code).is_none(),
+            "opcode {} is already taken or doesn't fit in two digits",
+            code
+        );
+        assert!(inputs <= 3, "opcodes take at most three parameters");
+
+        Self {
+            code,
+            inputs,
+            stores: false,
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Like `new`, but with an extra parameter after the inputs for `Effect::Store` to store into
+    pub fn storing(
+        code: i64,
+        inputs: usize,
+        handler: impl Fn(&mut Interpreter, &[i64]) -> Effect + Send + Sync + 'static,
+    ) -> Self {
+        assert!(inputs < 3, "opcodes take at most three parameters");
+
+        Self {
+            stores: true,
+            ..Self::new(code, inputs, handler)
+        }
+    }
+
+    fn arity(&self) -> usize {
+        self.inputs + self.stores as usize
+    }
+}
+
+/// A set of extension opcodes, laid out by full instruction word like the jump table
+pub struct Extensions {
+    extensions: Vec<Extension>,
+
+    /// Which extension, if any, handles each opcode with each combination of parameter modes
+    table: Vec<Option<u8>>,
+}
+
+/// How control flow carries on after an extension opcode
+pub(crate) enum Flow {
+    Continue,
+    Pause,
+    Halt,
+}
+
+impl Extensions {
+    pub fn new(extensions: Vec<Extension>) -> Self {
+        let mut table = vec![None; 22299];
+
+        for (idx, extension) in extensions.iter().enumerate() {
+            assert!(
+                table[extension.code as usize].is_none(),
+                "opcode {} was registered twice",
+                extension.code
+            );
+
+            // Every mode is allowed for inputs, but output parameters can't be immediate
+            let mut words = vec![extension.code];
+            for i in 0..extension.arity() {
+                let place = 10i64.pow(i as u32 + 2);
+                let modes: &[i64] = if extension.stores && i == extension.inputs {
+                    &[0, 2]
+                } else {
+                    &[0, 1, 2]
+                };
+
+                words = words
+                    .into_iter()
+                    .flat_map(|word| modes.iter().map(move |mode| word + mode * place))
+                    .collect();
+            }
+
+            for word in words {
+                table[word as usize] = Some(idx as u8);
+            }
+        }
+
+        Self { extensions, table }
+    }
+
+    fn get(&self, word: i64) -> Option<&Extension> {
+        let idx = (*self.table.get(usize::try_from(word).ok()?)?)?;
+        Some(&self.extensions[idx as usize])
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(self.extensions.iter().map(|extension| extension.code))
+            .finish()
+    }
+}
+
+impl Interpreter {
+    /// How many cells the extension opcode at the program counter takes up with its parameters, if
+    /// there is one there
+    pub(crate) fn extension_size(&self) -> Option<usize> {
+        let word = *self.memory.get(self.pc)?;
+        Some(1 + self.extensions.as_ref()?.get(word)?.arity())
+    }
+
+    /// Run the extension opcode at the program counter, going through `memory` for its parameters
+    /// like `step_on` does and panicking if it isn't one
+    #[cold]
+    pub(crate) fn extension(&mut self, memory: &mut impl Memory) -> Flow {
+        let pc = self.pc;
+        let word = self.memory.get(pc).copied();
+        let extensions = self.extensions.clone();
+        let extension = match (&extensions, word) {
+            (Some(extensions), Some(word)) => extensions.get(word),
+            _ => None,
+        }
+        .unwrap_or_else(|| panic!("invalid instruction {:?} at address {}", word, pc));
+
+        let mut modes = word.unwrap() / 100;
+        let mut params = [Parameter {
+            mode: Mode::Position,
+            value: 0,
+        }; 3];
+        for (i, param) in params.iter_mut().enumerate().take(extension.arity()) {
+            param.mode = Mode::from_digit(modes % 10).unwrap();
+            param.value = self.memory[pc + 1 + i];
+            modes /= 10;
+        }
+
+        let mut args = [0; 3];
+        for (arg, &param) in args.iter_mut().zip(&params[..extension.inputs]) {
+            *arg = self.load_on(memory, param);
+        }
+
+        // The output's address is worked out beforehand like the standard opcodes do, in case the
+        // handler moves the relative base
+        let target = if extension.stores {
+            Some(self.address(params[extension.inputs]))
+        } else {
+            None
+        };
+
+        self.pc = pc + 1 + extension.arity();
+        match (extension.handler)(self, &args[..extension.inputs]) {
+            Effect::Continue => Flow::Continue,
+
+            Effect::Store(value) => {
+                let target = target.unwrap_or_else(|| {
+                    panic!("opcode {} has no parameter to store into", extension.code)
+                });
+                memory.write(&mut self.memory, target, value);
+                Flow::Continue
+            }
+
+            Effect::Pause => Flow::Pause,
+
+            Effect::Halt(code) => {
+                self.pc = pc;
+                self.done = true;
+                self.exit_code = Some(code);
+                Flow::Halt
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the program both with `run` and a step at a time, checking they end up in the same state
+    fn run(interpreter: Interpreter) -> Interpreter {
+        let mut stepped = interpreter.clone();
+        while stepped.step() {}
+
+        let mut ran = interpreter;
+        ran.run();
+        assert_eq!(ran, stepped);
+        ran
+    }
+
+    #[test]
+    fn input_modes() {
+        let interpreter = Interpreter::new(vec![
+            109, 10, // arb 10
+            21060, 12, 7, 3, // ext [12], 7, [rb + 3]
+            22260, 1, 2, 3,  // ext [rb + 1], [rb + 2], [rb + 3]
+            99, //
+            4, 5, 9,
+        ])
+        .with_extensions(vec![Extension::new(60, 3, |interpreter, args| {
+            interpreter.output.extend(args);
+            Effect::Continue
+        })]);
+
+        // The second instruction is past the end of the jump table
+        let interpreter = run(interpreter);
+        assert!(interpreter.done);
+        assert_eq!(interpreter.output, [5, 7, 9, 4, 5, 9]);
+    }
+
+    fn increment() -> Extension {
+        Extension::storing(61, 1, |_, args| Effect::Store(args[0] + 1))
+    }
+
+    #[test]
+    fn output_modes() {
+        let interpreter = Interpreter::new(vec![
+            109, 10, // arb 10
+            61, 12, 13, // inc [12] -> [13]
+            2061, 12, 4,  // inc [12] -> [rb + 4]
+            99, //
+            0, 0, 0, //
+            5, 0, 0,
+        ])
+        .with_extensions(vec![increment()]);
+
+        let interpreter = run(interpreter);
+        assert!(interpreter.done);
+        assert_eq!(interpreter.memory[12..], [5, 6, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid instruction Some(1061) at address 0")]
+    fn immediate_output() {
+        Interpreter::new(vec![1061, 5, 0, 99])
+            .with_extensions(vec![increment()])
+            .step();
+    }
+
+    #[test]
+    fn pause() {
+        let interpreter = Interpreter::new(vec![
+            104, 1,  // out 1
+            62, // pause
+            104, 2, // out 2
+            99,
+        ])
+        .with_extensions(vec![Extension::new(62, 0, |_, _| Effect::Pause)]);
+
+        // Pausing leaves the program counter after the opcode, so running again carries on
+        let interpreter = run(interpreter);
+        assert!(!interpreter.done);
+        assert_eq!(interpreter.pc, 3);
+        assert_eq!(interpreter.output, [1]);
+
+        let interpreter = run(interpreter);
+        assert!(interpreter.done);
+        assert_eq!(interpreter.output, [1, 2]);
+    }
+
+    #[test]
+    fn halt() {
+        let interpreter = Interpreter::new(vec![
+            63, // exit 3
+            104, 1, // out 1
+            99,
+        ])
+        .with_extensions(vec![Extension::new(63, 0, |_, _| Effect::Halt(3))]);
+
+        // Like `99`, halting leaves the program counter on the opcode
+        let interpreter = run(interpreter);
+        assert!(interpreter.done);
+        assert_eq!(interpreter.exit_code, Some(3));
+        assert_eq!(interpreter.pc, 0);
+        assert!(interpreter.output.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "opcode 50 was registered twice")]
+    fn registered_twice() {
+        Extensions::new(vec![
+            Extension::new(50, 0, |_, _| Effect::Continue),
+            Extension::new(50, 1, |_, _| Effect::Continue),
+        ]);
+    }
+}
@@ -0,0 +1,146 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use crate::{Interpreter, Memory};
+
+/// Hash a single memory cell. Zero cells hash to zero so that growing memory with zeroes doesn't
+/// change the fingerprint.
+fn cell(idx: usize, value: i64) -> u64 {
+    if value == 0 {
+        return 0;
+    }
+
+    // splitmix64's finaliser
+    let mut z = (idx as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ value as u64;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// A running hash of an interpreter's memory, the sum of a hash of each cell. Computing it takes a
+/// pass over memory, but running the interpreter through it keeps it up to date as the program
+/// stores into memory for the cost of a couple of hashes per store, so the fingerprints of a
+/// search's states come almost for free.
+///
+/// Writes made by the host directly into `Interpreter::memory` aren't seen, so those should go
+/// through `write` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    memory: u64,
+}
+
+impl Fingerprint {
+    pub fn new(interpreter: &Interpreter) -> Self {
+        Self {
+            memory: interpreter
+                .memory
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (idx, &value)| acc.wrapping_add(cell(idx, value))),
+        }
+    }
+
+    /// Store into the interpreter's memory on behalf of the host
+    pub fn write(&mut self, interpreter: &mut Interpreter, idx: usize, value: i64) {
+        Memory::write(self, &mut interpreter.memory, idx, value);
+    }
+
+    /// Execute a single instruction like `Interpreter::step`
+    pub fn step(&mut self, interpreter: &mut Interpreter) -> bool {
+        interpreter.step_on(self)
+    }
+
+    /// Run the interpreter until it halts or needs more input, like `Interpreter::run`
+    pub fn run(&mut self, interpreter: &mut Interpreter) {
+        while interpreter.step_on(self) {}
+    }
+
+    /// Fingerprint the interpreter's whole state, its memory along with everything else it's
+    /// compared by. Interpreters which are equal have equal fingerprints.
+    pub fn of(&self, interpreter: &Interpreter) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.memory.hash(&mut hasher);
+        interpreter.pc.hash(&mut hasher);
+        interpreter.relative_base.hash(&mut hasher);
+        interpreter.done.hash(&mut hasher);
+        interpreter.exit_code.hash(&mut hasher);
+        interpreter.input.hash(&mut hasher);
+        interpreter.output.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Memory for Fingerprint {
+    fn read(&mut self, memory: &[i64], idx: usize) -> i64 {
+        memory[idx]
+    }
+
+    fn write(&mut self, memory: &mut [i64], idx: usize, value: i64) {
+        self.memory = self
+            .memory
+            .wrapping_sub(cell(idx, memory[idx]))
+            .wrapping_add(cell(idx, value));
+        memory[idx] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extension::{Effect, Extension};
+
+    fn hash(interpreter: &Interpreter) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        interpreter.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn incremental_matches_fresh() {
+        // Counts to 50 in [100], keeping whether it's done yet in [101]
+        let mut interpreter = Interpreter::new(vec![
+            1101, 0, 0, 100, // add 0, 0 -> [100]
+            1001, 100, 1, 100, // add [100], 1 -> [100]
+            1007, 100, 50, 101, // lt [100], 50 -> [101]
+            1005, 101, 4, // jnz [101], 4
+            99,
+        ]);
+        interpreter.memory.resize(102, 0);
+
+        let mut fingerprint = Fingerprint::new(&interpreter);
+        fingerprint.run(&mut interpreter);
+        assert_eq!(interpreter.memory[100], 50);
+        assert_eq!(fingerprint, Fingerprint::new(&interpreter));
+
+        fingerprint.write(&mut interpreter, 3, 7);
+        assert_eq!(fingerprint, Fingerprint::new(&interpreter));
+
+        // Growing memory with zeroes doesn't change anything
+        interpreter.memory.resize(1000, 0);
+        assert_eq!(fingerprint, Fingerprint::new(&interpreter));
+        assert_ne!(fingerprint, Fingerprint::new(&Interpreter::new(vec![99])));
+    }
+
+    #[test]
+    fn extensions_are_ignored() {
+        let plain = Interpreter::new(vec![104, 1, 99]);
+        let extended = plain
+            .clone()
+            .with_extensions(vec![Extension::new(50, 0, |_, _| Effect::Continue)]);
+
+        assert_eq!(plain, extended);
+        assert_eq!(hash(&plain), hash(&extended));
+
+        let fingerprint = Fingerprint::new(&plain);
+        assert_eq!(fingerprint, Fingerprint::new(&extended));
+        assert_eq!(fingerprint.of(&plain), fingerprint.of(&extended));
+
+        // Whereas anything else about the state isn't
+        let mut moved = plain.clone();
+        moved.pc = 2;
+        assert_ne!(plain, moved);
+        assert_ne!(fingerprint.of(&plain), fingerprint.of(&moved));
+    }
+}
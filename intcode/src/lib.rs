@@ -1,5 +1,60 @@
-use std::{collections::VecDeque, hint::unreachable_unchecked};
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::{borrow::ToOwned, collections::VecDeque, string::String, sync::Arc, vec::Vec};
+use core::{
+    error, fmt,
+    hash::{Hash, Hasher},
+};
+
+pub mod ascii;
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod cache;
+pub mod coverage;
+pub mod decode;
+pub mod extension;
+#[cfg(feature = "std")]
+pub mod fingerprint;
+pub mod mmio;
+pub mod records;
+#[cfg(feature = "std")]
+pub mod script;
+mod sizing;
+#[cfg(feature = "std")]
+pub mod symbolic;
+#[doc(hidden)]
+pub mod testing;
+pub mod transpile;
+
+use decode::{Instruction, Mode, Op, Parameter};
+use extension::{Extension, Extensions, Flow};
+
+pub use cache::BlockCache;
+
+/// Why a program couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Which of the program's values is wrong
+    pub index: usize,
+    pub value: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "value {} of the program, {:?}, isn't an integer",
+            self.index, self.value
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Interpreters compare and hash by the state of the program they're running, which leaves out
+/// their extensions
 #[derive(Debug, Clone)]
 pub struct Interpreter {
     pub memory: Vec<i64>,
@@ -12,14 +67,103 @@ pub struct Interpreter {
     pub done: bool,
 
     pub relative_base: i64,
+
+    /// The code the program halted with through an extension opcode, if it did
+    pub exit_code: Option<i64>,
+
+    pub extensions: Option<Arc<Extensions>>,
+}
+
+impl PartialEq for Interpreter {
+    fn eq(&self, other: &Self) -> bool {
+        self.pc == other.pc
+            && self.relative_base == other.relative_base
+            && self.done == other.done
+            && self.exit_code == other.exit_code
+            && self.input == other.input
+            && self.output == other.output
+            && self.memory == other.memory
+    }
+}
+
+impl Eq for Interpreter {}
+
+impl Hash for Interpreter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pc.hash(state);
+        self.relative_base.hash(state);
+        self.done.hash(state);
+        self.exit_code.hash(state);
+        self.input.hash(state);
+        self.output.hash(state);
+        self.memory.hash(state);
+    }
 }
 
 type Opcode = fn(&mut Interpreter);
 
+/// How `Interpreter::step_on` gets at the cells instructions load from and store into
+pub(crate) trait Memory {
+    fn read(&mut self, memory: &[i64], idx: usize) -> i64;
+    fn write(&mut self, memory: &mut [i64], idx: usize, value: i64);
+}
+
+/// Plain memory, with nothing mapped into it
+struct Plain;
+
+impl Memory for Plain {
+    #[inline(always)]
+    fn read(&mut self, memory: &[i64], idx: usize) -> i64 {
+        memory[idx]
+    }
+
+    #[inline(always)]
+    fn write(&mut self, memory: &mut [i64], idx: usize, value: i64) {
+        memory[idx] = value;
+    }
+}
+
+/// Plain memory which remembers the last store into any of the addresses being watched, for
+/// running single instructions on behalf of compiled code which relies on those not changing
+pub(crate) struct Watched<'a> {
+    watched: &'a [bool],
+    pub(crate) overwrote: Option<usize>,
+}
+
+impl<'a> Watched<'a> {
+    pub(crate) fn new(watched: &'a [bool]) -> Self {
+        Self {
+            watched,
+            overwrote: None,
+        }
+    }
+}
+
+impl Memory for Watched<'_> {
+    fn read(&mut self, memory: &[i64], idx: usize) -> i64 {
+        memory[idx]
+    }
+
+    fn write(&mut self, memory: &mut [i64], idx: usize, value: i64) {
+        memory[idx] = value;
+        if self.watched.get(idx) == Some(&true) {
+            self.overwrote = Some(idx);
+        }
+    }
+}
+
+/// How much stack to give a thread which runs long programs with `Interpreter::run`. Every
+/// instruction run is a call deeper, and debug builds recurse deeply enough to need more than a
+/// thread gets by default.
+pub const STACK_SIZE: usize = 256 * 1024 * 1024;
+
 // Map each opcode to a function applying its effects to the interpreter. The effects include
 // continuing execution, and so if an opcode function does not call `interpreter.run()` then
 // execution is paused. This is so that we don't need to use a call stack and can just jump around
-// from function to function.
+// from function to function. Opcodes missing from the table, or past its end, are left to the
+// interpreter's extensions. Lookups only ever borrow the table, so it's promoted to a single copy
+// in static memory rather than copied at every use.
+#[allow(clippy::large_const_arrays)]
 pub const JUMP_TABLE: [Option<Opcode>; 22209] = {
     let mut jump_table = [None; 22209];
 
@@ -211,30 +355,139 @@ impl Interpreter {
             pc: 0,
             done: false,
             relative_base: 0,
+            exit_code: None,
+            extensions: None,
         }
     }
 
+    /// Handle the given opcodes on top of the standard ones
+    pub fn with_extensions(mut self, extensions: Vec<Extension>) -> Self {
+        self.extensions = Some(Arc::new(Extensions::new(extensions)));
+        self
+    }
+
     pub fn run(&mut self) {
-        debug_assert!(JUMP_TABLE[self.memory[self.pc] as usize].is_some());
-
-        unsafe {
-            let opcode = *self.memory.get_unchecked(self.pc);
-            self.pc += 1;
-            match JUMP_TABLE.get_unchecked(opcode as usize) {
-                Some(opcode) => opcode(self),
-                None => unreachable_unchecked(),
+        debug_assert!(self.pc < self.memory.len());
+
+        let opcode = unsafe { *self.memory.get_unchecked(self.pc) };
+        self.pc += 1;
+        match JUMP_TABLE.get(opcode as usize) {
+            Some(Some(opcode)) => opcode(self),
+            _ => {
+                self.pc -= 1;
+                if let Flow::Continue = self.extension(&mut Plain) {
+                    self.run();
+                }
+            }
+        }
+    }
+
+    /// Execute a single instruction, returning whether one was executed rather than the program
+    /// halting, waiting for input or being paused by an extension. This doesn't go through the
+    /// jump table and so, unlike `run`, never recurses.
+    pub fn step(&mut self) -> bool {
+        self.step_on(&mut Plain)
+    }
+
+    /// Execute a single instruction like `step`, going through `memory` for the cells its
+    /// parameters refer to. The instruction itself is always fetched straight from `self.memory`.
+    pub(crate) fn step_on(&mut self, memory: &mut impl Memory) -> bool {
+        let instruction = match Instruction::decode(&self.memory, self.pc) {
+            Some(instruction) => instruction,
+            None => return matches!(self.extension(memory), Flow::Continue),
+        };
+        let params = instruction.params();
+        let next = self.pc + instruction.size();
+
+        match instruction.op {
+            Op::Add | Op::Mul | Op::LessThan | Op::Equals => {
+                let a = self.load_on(memory, params[0]);
+                let b = self.load_on(memory, params[1]);
+                let value = match instruction.op {
+                    Op::Add => a + b,
+                    Op::Mul => a * b,
+                    Op::LessThan => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+                self.store_on(memory, params[2], value);
+            }
+
+            Op::Input => match self.input.pop_front() {
+                Some(input) => self.store_on(memory, params[0], input),
+                None => return false,
+            },
+
+            Op::Output => {
+                let value = self.load_on(memory, params[0]);
+                self.output.push_back(value);
+            }
+
+            Op::JumpIfTrue | Op::JumpIfFalse => {
+                let condition = self.load_on(memory, params[0]) != 0;
+                if condition == (instruction.op == Op::JumpIfTrue) {
+                    self.pc = self.load_on(memory, params[1]) as usize;
+                    return true;
+                }
             }
+
+            Op::AdjustBase => self.relative_base += self.load_on(memory, params[0]),
+
+            Op::Halt => {
+                self.done = true;
+                return false;
+            }
+        }
+
+        self.pc = next;
+        true
+    }
+
+    /// The address a parameter refers to, if it isn't immediate
+    pub(crate) fn address(&self, param: Parameter) -> usize {
+        match param.mode {
+            Mode::Relative => (self.relative_base + param.value) as usize,
+            _ => param.value as usize,
+        }
+    }
+
+    fn load(&self, param: Parameter) -> i64 {
+        match param.mode {
+            Mode::Immediate => param.value,
+            _ => self.memory[self.address(param)],
         }
     }
 
+    fn load_on(&self, memory: &mut impl Memory, param: Parameter) -> i64 {
+        match param.mode {
+            Mode::Immediate => param.value,
+            _ => memory.read(&self.memory, self.address(param)),
+        }
+    }
+
+    fn store_on(&mut self, memory: &mut impl Memory, param: Parameter, value: i64) {
+        let idx = self.address(param);
+        memory.write(&mut self.memory, idx, value);
+    }
+
+    /// Parse a program written as integers separated by commas
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        input
+            .trim()
+            .split(',')
+            .enumerate()
+            .map(|(index, value)| {
+                value.trim().parse().map_err(|_| ParseError {
+                    index,
+                    value: value.to_owned(),
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+
+    /// Like `parse`, for programs which are known to be valid
     pub fn from_input(input: &str) -> Self {
-        Self::new(
-            input
-                .trim()
-                .split(',')
-                .map(|n| n.parse().unwrap())
-                .collect(),
-        )
+        Self::parse(input).unwrap_or_else(|err| panic!("{}", err))
     }
 
     pub fn input_from_ascii(&mut self, input: &str) {
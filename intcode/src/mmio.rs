@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{Interpreter, Memory};
+
+/// Something attached to a range of addresses, which sees the program's loads from and stores
+/// into that range instead of memory
+pub trait Device {
+    /// Produce the value the program loads from the cell `offset` cells into the device's range
+    fn read(&mut self, offset: usize) -> i64;
+
+    /// Accept the value the program stores into the cell `offset` cells into the device's range
+    fn write(&mut self, offset: usize, value: i64);
+}
+
+/// The simplest device, a plain region of cells the host can look at once the program's done with
+/// it, such as a framebuffer
+impl Device for Vec<i64> {
+    fn read(&mut self, offset: usize) -> i64 {
+        self[offset]
+    }
+
+    fn write(&mut self, offset: usize, value: i64) {
+        self[offset] = value;
+    }
+}
+
+/// A set of devices mapped into an interpreter's memory. Runs through the bus go an instruction at
+/// a time so that every load and store can be checked against the devices' ranges, and are
+/// slower than `Interpreter::run` because of it.
+///
+/// Only parameters go through the devices, instructions are always fetched straight from memory.
+#[derive(Default)]
+pub struct Bus<'a> {
+    devices: Vec<(Range<usize>, &'a mut dyn Device)>,
+}
+
+impl<'a> Bus<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map the device into the given range of addresses, shadowing whatever memory is there
+    pub fn attach(&mut self, range: Range<usize>, device: &'a mut dyn Device) {
+        let overlapping = self
+            .devices
+            .iter()
+            .find(|(other, _)| range.start < other.end && other.start < range.end);
+        if let Some((other, _)) = overlapping {
+            panic!("{:?} overlaps the device at {:?}", range, other);
+        }
+
+        self.devices.push((range, device));
+    }
+
+    fn device(&mut self, idx: usize) -> Option<(usize, &mut (dyn Device + 'a))> {
+        self.devices
+            .iter_mut()
+            .find(|(range, _)| range.contains(&idx))
+            .map(|(range, device)| (idx - range.start, &mut **device))
+    }
+
+    /// Execute a single instruction like `Interpreter::step`
+    pub fn step(&mut self, interpreter: &mut Interpreter) -> bool {
+        interpreter.step_on(self)
+    }
+
+    /// Run the interpreter until it halts or needs more input, like `Interpreter::run`
+    pub fn run(&mut self, interpreter: &mut Interpreter) {
+        while interpreter.step_on(self) {}
+    }
+}
+
+impl Memory for Bus<'_> {
+    fn read(&mut self, memory: &[i64], idx: usize) -> i64 {
+        match self.device(idx) {
+            Some((offset, device)) => device.read(offset),
+            None => memory[idx],
+        }
+    }
+
+    fn write(&mut self, memory: &mut [i64], idx: usize, value: i64) {
+        match self.device(idx) {
+            Some((offset, device)) => device.write(offset, value),
+            None => memory[idx] = value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// A device which reads as how many times it's been read, and remembers what's stored into it
+    #[derive(Default)]
+    struct Clock {
+        ticks: i64,
+        stored: Vec<(usize, i64)>,
+    }
+
+    impl Device for Clock {
+        fn read(&mut self, _: usize) -> i64 {
+            self.ticks += 1;
+            self.ticks
+        }
+
+        fn write(&mut self, offset: usize, value: i64) {
+            self.stored.push((offset, value));
+        }
+    }
+
+    #[test]
+    fn devices_and_memory() {
+        let mut interpreter = Interpreter::new(vec![
+            1, 100, 100, 200, // add [100], [100] -> [200]
+            1001, 15, 5, 16, // add [15], 5 -> [16]
+            4, 201, // out [201]
+            1101, 4, 5, 101, // add 4, 5 -> [101]
+            99,  //
+            7, 0,
+        ]);
+
+        let mut clock = Clock::default();
+        let mut framebuffer = vec![0, 9];
+        let mut bus = Bus::new();
+        bus.attach(100..102, &mut clock);
+        bus.attach(200..202, &mut framebuffer);
+        bus.run(&mut interpreter);
+
+        assert!(interpreter.done);
+        assert_eq!(interpreter.output, [9]);
+        assert_eq!(clock.ticks, 2);
+        assert_eq!(clock.stored, [(1, 9)]);
+        assert_eq!(framebuffer, [3, 9]);
+
+        // Memory outside the devices is used as is, and never grows to cover them
+        assert_eq!(interpreter.memory[16], 12);
+        assert_eq!(interpreter.memory.len(), 17);
+    }
+
+    #[test]
+    fn adjacent_devices() {
+        // The program sits after the devices so that the cells either side of the boundary
+        // between them can be used
+        let mut memory = vec![0; 20];
+        memory.extend_from_slice(&[
+            1, 9, 10, 9, // add [9], [10] -> [9]
+            1101, 5, 6, 10, // add 5, 6 -> [10]
+            99,
+        ]);
+        let mut interpreter = Interpreter::new(memory);
+        interpreter.pc = 20;
+
+        let (mut a, mut b) = (vec![0; 10], vec![0; 10]);
+        a[9] = 4;
+        b[0] = 3;
+        let mut bus = Bus::new();
+        bus.attach(0..10, &mut a);
+        bus.attach(10..20, &mut b);
+        bus.run(&mut interpreter);
+
+        assert!(interpreter.done);
+        assert_eq!(a, [0, 0, 0, 0, 0, 0, 0, 0, 0, 7]);
+        assert_eq!(b, [11, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(&interpreter.memory[..20], &[0; 20][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "5..15 overlaps the device at 0..10")]
+    fn overlapping_devices() {
+        let (mut a, mut b) = (vec![0; 10], vec![0; 10]);
+        let mut bus = Bus::new();
+        bus.attach(0..10, &mut a);
+        bus.attach(5..15, &mut b);
+    }
+}
@@ -0,0 +1,212 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{error, fmt, marker::PhantomData};
+
+use crate::Interpreter;
+
+/// Something a program outputs as a fixed number of values at a time, such as a pixel's
+/// coordinates and colour
+pub trait Record: Sized {
+    const SIZE: usize;
+
+    /// Decode the record from exactly `SIZE` values, returning `None` if they don't make sense
+    fn decode(values: &[i64]) -> Option<Self>;
+}
+
+/// The plainest record, just the values themselves
+impl<const N: usize> Record for [i64; N] {
+    const SIZE: usize = N;
+
+    fn decode(values: &[i64]) -> Option<Self> {
+        let mut record = [0; N];
+        record.copy_from_slice(values);
+        Some(record)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The output ran out partway through a record, which is left in the output
+    Truncated { expected: usize, found: Vec<i64> },
+
+    /// A whole record's values which didn't decode
+    Invalid(Vec<i64>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Truncated { expected, found } => write!(
+                f,
+                "expected a record of {} values but the output ran out after {:?}",
+                expected, found
+            ),
+            Error::Invalid(values) => write!(f, "{:?} isn't a valid record", values),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+fn decode<R: Record>(output: &mut VecDeque<i64>) -> Result<R, Error> {
+    if output.len() < R::SIZE {
+        return Err(Error::Truncated {
+            expected: R::SIZE,
+            found: output.iter().copied().collect(),
+        });
+    }
+
+    let values: Vec<_> = output.drain(..R::SIZE).collect();
+    R::decode(&values).ok_or(Error::Invalid(values))
+}
+
+/// An iterator draining an interpreter's output a record at a time, see `Interpreter::records`
+pub struct Records<'a, R> {
+    output: &'a mut VecDeque<i64>,
+
+    /// Whether a truncated record has been returned, as it stays in the output and so would just
+    /// be returned over and over again
+    truncated: bool,
+
+    record: PhantomData<R>,
+}
+
+impl<R: Record> Iterator for Records<'_, R> {
+    type Item = Result<R, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.output.is_empty() || self.truncated {
+            return None;
+        }
+
+        let record = decode(self.output);
+        self.truncated = matches!(record, Err(Error::Truncated { .. }));
+        Some(record)
+    }
+}
+
+impl Interpreter {
+    /// Take a single record from the front of the output
+    pub fn record<R: Record>(&mut self) -> Result<R, Error> {
+        decode(&mut self.output)
+    }
+
+    /// Drain the output a record at a time. If it ends with part of a record, that's an error and
+    /// the part is left in the output.
+    pub fn records<R: Record>(&mut self) -> Records<'_, R> {
+        Records {
+            output: &mut self.output,
+            truncated: false,
+            record: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Pixel {
+        x: i64,
+        y: i64,
+        lit: bool,
+    }
+
+    impl Record for Pixel {
+        const SIZE: usize = 3;
+
+        fn decode(values: &[i64]) -> Option<Self> {
+            Some(Self {
+                x: values[0],
+                y: values[1],
+                lit: match values[2] {
+                    0 => false,
+                    1 => true,
+                    _ => return None,
+                },
+            })
+        }
+    }
+
+    /// A program outputting each of the values given
+    fn outputting(values: &[i64]) -> Interpreter {
+        let mut memory: Vec<_> = values.iter().flat_map(|&value| vec![104, value]).collect();
+        memory.push(99);
+
+        let mut interpreter = Interpreter::new(memory);
+        interpreter.run();
+        interpreter
+    }
+
+    #[test]
+    fn round_trip() {
+        let pixels = [
+            Pixel {
+                x: 3,
+                y: -1,
+                lit: true,
+            },
+            Pixel {
+                x: 0,
+                y: 7,
+                lit: false,
+            },
+        ];
+        let values: Vec<_> = pixels
+            .iter()
+            .flat_map(|pixel| vec![pixel.x, pixel.y, pixel.lit as i64])
+            .collect();
+
+        let mut interpreter = outputting(&values);
+        let decoded: Result<Vec<Pixel>, _> = interpreter.records().collect();
+        assert_eq!(decoded, Ok(pixels.to_vec()));
+        assert!(interpreter.output.is_empty());
+
+        let mut interpreter = outputting(&values);
+        assert_eq!(interpreter.record(), Ok([3, -1, 1]));
+        assert_eq!(interpreter.record(), Ok(pixels[1]));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut interpreter = outputting(&[1, 2, 0, 4, 5]);
+        let records: Vec<_> = interpreter.records::<Pixel>().collect();
+        assert_eq!(
+            records,
+            [
+                Ok(Pixel {
+                    x: 1,
+                    y: 2,
+                    lit: false,
+                }),
+                Err(Error::Truncated {
+                    expected: 3,
+                    found: vec![4, 5],
+                }),
+            ]
+        );
+
+        // What there was of the record is left in the output
+        assert_eq!(interpreter.output, [4, 5]);
+    }
+
+    #[test]
+    fn invalid() {
+        let mut interpreter = outputting(&[1, 2, 3, 4, 5, 1]);
+        assert_eq!(
+            interpreter.record::<Pixel>(),
+            Err(Error::Invalid(vec![1, 2, 3]))
+        );
+
+        // The invalid record is drained so the next one can still be read
+        assert_eq!(
+            interpreter.record(),
+            Ok(Pixel {
+                x: 4,
+                y: 5,
+                lit: true,
+            })
+        );
+    }
+}
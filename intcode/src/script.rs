@@ -0,0 +1,255 @@
+use std::{collections::HashMap, convert::TryFrom, error, fmt};
+
+use crate::{ascii, Interpreter};
+
+mod pattern;
+
+pub use pattern::Pattern;
+
+/// A single line of a script
+#[derive(Debug, Clone)]
+enum Command {
+    Send(String),
+    Expect(Pattern),
+    If(Pattern, usize),
+    Goto(usize),
+    Fail(String),
+}
+
+/// A small expect-style script for driving programs which talk in ASCII, so that routes through
+/// text adventures can be shared and replayed without writing any Rust. Each line is one of:
+///
+/// - `send <text>` sends the text as a line of input and runs the program until it wants more.
+/// - `expect <pattern>` consumes the program's output up to the end of the first match of the
+///   pattern, failing if there's none.
+/// - `if <pattern> goto <label>` does the same as `expect` and jumps to the label if there's a
+///   match, and otherwise carries on without consuming anything.
+/// - `goto <label>` jumps to the label.
+/// - `fail <text>` stops the script with an error.
+/// - `<label>:` marks where jumps to the label go.
+///
+/// Blank lines and lines starting with `#` are ignored. Patterns are regular expressions, see
+/// `Pattern`, and whatever their named groups capture is stored in variables which `send` and
+/// `fail` substitute for `$name` or `${name}`. A value the program outputs which isn't an ASCII
+/// character, usually its answer, is stored in the `answer` variable.
+#[derive(Debug, Clone)]
+pub struct Script {
+    /// Every command along with its line number
+    commands: Vec<(usize, Command)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse {
+        line: usize,
+        message: String,
+    },
+
+    /// An `expect` didn't match, leaving the output it couldn't match against
+    NoMatch {
+        line: usize,
+        output: String,
+    },
+
+    /// The script tried sending a line to a program which had already halted
+    Halted {
+        line: usize,
+    },
+
+    Failed {
+        line: usize,
+        message: String,
+    },
+    Ascii {
+        line: usize,
+        error: ascii::Error,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Error::NoMatch { line, output } => {
+                write!(f, "line {}: no match in output:\n{}", line, output)
+            }
+            Error::Halted { line } => write!(f, "line {}: the program has halted", line),
+            Error::Failed { line, message } => write!(f, "line {}: {}", line, message),
+            Error::Ascii { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Replace every `$name` and `${name}` in the text with the variable's value
+fn substitute(text: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(dollar) = rest.find('$') {
+        result += &rest[..dollar];
+        rest = &rest[dollar + 1..];
+
+        let (name, after) = if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced.find('}').ok_or("unclosed variable")?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            rest.split_at(end)
+        };
+
+        result += variables
+            .get(name)
+            .ok_or_else(|| format!("unknown variable {:?}", name))?;
+        rest = after;
+    }
+
+    result += rest;
+    Ok(result)
+}
+
+/// The label a line defines, if it's a single word followed by a colon
+fn label_definition(text: &str) -> Option<&str> {
+    text.strip_suffix(':').filter(|label| !label.contains(' '))
+}
+
+impl Script {
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let lines: Vec<_> = source
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        // Labels refer to the index of the command after them
+        let mut labels = HashMap::new();
+        let mut count = 0;
+        for &(line, text) in &lines {
+            match label_definition(text) {
+                Some(label) => {
+                    if labels.insert(label, count).is_some() {
+                        return Err(Error::Parse {
+                            line,
+                            message: format!("label {:?} is defined twice", label),
+                        });
+                    }
+                }
+                None => count += 1,
+            }
+        }
+
+        let mut commands = Vec::new();
+        for (line, text) in lines {
+            let parse_error = |message: String| Error::Parse { line, message };
+            let pattern = |source: &str| Pattern::new(source).map_err(parse_error);
+            let label = |name: &str| match labels.get(name) {
+                Some(&target) => Ok(target),
+                None => Err(parse_error(format!("unknown label {:?}", name))),
+            };
+
+            if label_definition(text).is_some() {
+                continue;
+            }
+
+            let (keyword, argument) = match text.find(' ') {
+                Some(space) => (&text[..space], text[space + 1..].trim()),
+                None => (text, ""),
+            };
+
+            let command = match keyword {
+                "send" => Command::Send(argument.to_owned()),
+                "expect" => Command::Expect(pattern(argument)?),
+                "goto" => Command::Goto(label(argument)?),
+                "fail" => Command::Fail(argument.to_owned()),
+
+                "if" => {
+                    let goto = argument.rfind(" goto ").ok_or_else(|| {
+                        parse_error("expected `if <pattern> goto <label>`".into())
+                    })?;
+                    Command::If(
+                        pattern(&argument[..goto])?,
+                        label(argument[goto + 6..].trim())?,
+                    )
+                }
+
+                _ => return Err(parse_error(format!("unknown command {:?}", keyword))),
+            };
+
+            commands.push((line, command));
+        }
+
+        Ok(Self { commands })
+    }
+
+    /// Run the script against the program, returning the variables it ended up with
+    pub fn run(&self, interpreter: &mut Interpreter) -> Result<HashMap<String, String>, Error> {
+        let mut variables = HashMap::new();
+        let mut output = String::new();
+
+        // Whatever the program says before it first wants input is the first thing to match. Text
+        // programs can run for a long time between inputs, so they're stepped rather than run to
+        // keep debug builds from recursing deeply.
+        while interpreter.step() {}
+
+        let mut idx = 0;
+        while let Some((line, command)) = self.commands.get(idx) {
+            let line = *line;
+            idx += 1;
+
+            for value in interpreter.output.drain(..) {
+                match u8::try_from(value) {
+                    Ok(byte) if byte.is_ascii() => output.push(byte as char),
+                    _ => {
+                        variables.insert("answer".to_owned(), value.to_string());
+                    }
+                }
+            }
+
+            let substitute = |text: &str, variables: &HashMap<_, _>| {
+                substitute(text, variables).map_err(|message| Error::Failed { line, message })
+            };
+
+            match command {
+                Command::Send(text) => {
+                    if interpreter.done {
+                        return Err(Error::Halted { line });
+                    }
+
+                    let text = substitute(text, &variables)?;
+                    ascii::send_line(interpreter, &text)
+                        .map_err(|error| Error::Ascii { line, error })?;
+                    while interpreter.step() {}
+                }
+
+                Command::Expect(pattern) | Command::If(pattern, _) => {
+                    let found = match pattern.find(&output) {
+                        Some(found) => found,
+                        None if matches!(command, Command::If(..)) => continue,
+                        None => return Err(Error::NoMatch { line, output }),
+                    };
+
+                    for (name, range) in found.named {
+                        variables.insert(name, output[range].to_owned());
+                    }
+                    output.drain(..found.range.end);
+
+                    if let Command::If(_, target) = command {
+                        idx = *target;
+                    }
+                }
+
+                Command::Goto(target) => idx = *target,
+
+                Command::Fail(text) => {
+                    let message = substitute(text, &variables)?;
+                    return Err(Error::Failed { line, message });
+                }
+            }
+        }
+
+        Ok(variables)
+    }
+}
@@ -0,0 +1,415 @@
+use std::ops::Range;
+
+/// A single piece of a parsed pattern
+#[derive(Debug, Clone)]
+enum Node {
+    Byte(u8),
+
+    /// Any byte but a newline
+    Any,
+
+    Class {
+        ranges: Vec<(u8, u8)>,
+        negated: bool,
+    },
+
+    LineStart,
+    LineEnd,
+
+    Group {
+        inner: Box<Node>,
+
+        /// Which capture the group records, if it's capturing
+        capture: Option<usize>,
+    },
+
+    Sequence(Vec<Node>),
+    Alternatives(Vec<Node>),
+
+    Repeat {
+        inner: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+/// A small backtracking regular expression engine for scripts, supporting the usual syntax for
+/// classes, escapes, anchors, alternation, quantifiers and groups. Groups named with `(?<name>...)`
+/// are what scripts capture into variables. `^` and `$` match at the start and end of every line.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    root: Node,
+
+    /// The number of capturing groups
+    captures: usize,
+
+    /// The name of each named capturing group, along with its index
+    names: Vec<(String, usize)>,
+}
+
+/// Where a pattern matched, along with what each of its named groups captured
+pub struct Match {
+    pub range: Range<usize>,
+    pub named: Vec<(String, Range<usize>)>,
+}
+
+struct Parser<'a> {
+    pattern: &'a [u8],
+    pos: usize,
+    captures: usize,
+    names: Vec<(String, usize)>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.pattern.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next(&mut self) -> Result<u8, String> {
+        let byte = self.peek().ok_or("pattern ends unexpectedly")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn alternatives(&mut self) -> Result<Node, String> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.eat(b'|') {
+            alternatives.push(self.sequence()?);
+        }
+
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Node::Alternatives(alternatives)
+        })
+    }
+
+    fn sequence(&mut self) -> Result<Node, String> {
+        let mut sequence = Vec::new();
+        while !matches!(self.peek(), None | Some(b'|') | Some(b')')) {
+            sequence.push(self.repeat()?);
+        }
+        Ok(Node::Sequence(sequence))
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.pattern[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn repeat(&mut self) -> Result<Node, String> {
+        let mut node = self.atom()?;
+
+        loop {
+            let (min, max) = match self.peek() {
+                Some(b'*') => (0, None),
+                Some(b'+') => (1, None),
+                Some(b'?') => (0, Some(1)),
+                Some(b'{') => {
+                    self.pos += 1;
+                    let min = self.number().ok_or("expected a repetition count")?;
+                    let max = if self.eat(b',') {
+                        self.number()
+                    } else {
+                        Some(min)
+                    };
+                    if self.peek() != Some(b'}') {
+                        return Err("unterminated repetition count".to_owned());
+                    }
+                    (min, max)
+                }
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+
+            let greedy = !self.eat(b'?');
+            node = Node::Repeat {
+                inner: Box::new(node),
+                min,
+                max,
+                greedy,
+            };
+        }
+    }
+
+    fn escape(&mut self) -> Result<Node, String> {
+        let class = |ranges: &[(u8, u8)], negated| Node::Class {
+            ranges: ranges.to_vec(),
+            negated,
+        };
+
+        const DIGIT: &[(u8, u8)] = &[(b'0', b'9')];
+        const WORD: &[(u8, u8)] = &[(b'0', b'9'), (b'A', b'Z'), (b'a', b'z'), (b'_', b'_')];
+        const SPACE: &[(u8, u8)] = &[(b' ', b' '), (b'\t', b'\r')];
+
+        Ok(match self.next()? {
+            b'd' => class(DIGIT, false),
+            b'D' => class(DIGIT, true),
+            b'w' => class(WORD, false),
+            b'W' => class(WORD, true),
+            b's' => class(SPACE, false),
+            b'S' => class(SPACE, true),
+            b'n' => Node::Byte(b'\n'),
+            b't' => Node::Byte(b'\t'),
+            byte => Node::Byte(byte),
+        })
+    }
+
+    fn class(&mut self) -> Result<Node, String> {
+        let negated = self.eat(b'^');
+        let mut ranges = Vec::new();
+
+        // A leading `]` is part of the class rather than closing it
+        let mut first = true;
+        loop {
+            let low = match self.next().map_err(|_| "unterminated class")? {
+                b']' if !first => break,
+                b'\\' => match self.escape()? {
+                    Node::Byte(byte) => byte,
+                    Node::Class {
+                        ranges: escaped,
+                        negated: false,
+                    } => {
+                        ranges.extend(escaped);
+                        first = false;
+                        continue;
+                    }
+                    _ => return Err("negated escapes aren't allowed in classes".to_owned()),
+                },
+                byte => byte,
+            };
+            first = false;
+
+            let is_range =
+                self.peek() == Some(b'-') && self.pattern.get(self.pos + 1) != Some(&b']');
+            if is_range {
+                self.pos += 1;
+                let high = self.next()?;
+                ranges.push((low, high));
+            } else {
+                ranges.push((low, low));
+            }
+        }
+
+        Ok(Node::Class { ranges, negated })
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        Ok(match self.next()? {
+            b'.' => Node::Any,
+            b'^' => Node::LineStart,
+            b'$' => Node::LineEnd,
+            b'[' => self.class()?,
+            b'\\' => self.escape()?,
+
+            b'(' => {
+                let capture = if self.eat(b'?') {
+                    if self.eat(b':') {
+                        None
+                    } else if self.eat(b'<') {
+                        let start = self.pos;
+                        while self.peek().is_some_and(|byte| byte != b'>') {
+                            self.pos += 1;
+                        }
+                        let name = String::from_utf8_lossy(&self.pattern[start..self.pos]);
+                        if !self.eat(b'>') || name.is_empty() {
+                            return Err("expected a group name".to_owned());
+                        }
+
+                        self.captures += 1;
+                        self.names.push((name.into_owned(), self.captures - 1));
+                        Some(self.captures - 1)
+                    } else {
+                        return Err("unknown group kind".to_owned());
+                    }
+                } else {
+                    self.captures += 1;
+                    Some(self.captures - 1)
+                };
+
+                let inner = self.alternatives()?;
+                if !self.eat(b')') {
+                    return Err("unclosed group".to_owned());
+                }
+
+                Node::Group {
+                    inner: Box::new(inner),
+                    capture,
+                }
+            }
+
+            byte @ (b'*' | b'+' | b'?' | b'{' | b')') => {
+                return Err(format!("unexpected {:?}", byte as char))
+            }
+
+            byte => Node::Byte(byte),
+        })
+    }
+}
+
+type Captures = Vec<Option<Range<usize>>>;
+
+/// A continuation, called with where the pattern matched up to so far
+type Then<'a> = &'a mut dyn FnMut(usize, &mut Captures) -> bool;
+
+struct Matcher<'a> {
+    text: &'a [u8],
+}
+
+impl Matcher<'_> {
+    fn node(&self, node: &Node, pos: usize, captures: &mut Captures, then: Then) -> bool {
+        let text = self.text;
+        match node {
+            Node::Byte(byte) => text.get(pos) == Some(byte) && then(pos + 1, captures),
+            Node::Any => pos < text.len() && text[pos] != b'\n' && then(pos + 1, captures),
+
+            Node::Class { ranges, negated } => match text.get(pos) {
+                Some(&byte) => {
+                    let inside = ranges
+                        .iter()
+                        .any(|&(low, high)| (low..=high).contains(&byte));
+                    inside != *negated && then(pos + 1, captures)
+                }
+                None => false,
+            },
+
+            Node::LineStart => (pos == 0 || text[pos - 1] == b'\n') && then(pos, captures),
+            Node::LineEnd => (pos == text.len() || text[pos] == b'\n') && then(pos, captures),
+
+            Node::Group { inner, capture } => {
+                self.node(inner, pos, captures, &mut |end, captures| {
+                    let capture = match capture {
+                        Some(capture) => *capture,
+                        None => return then(end, captures),
+                    };
+
+                    let old = captures[capture].replace(pos..end);
+                    then(end, captures) || {
+                        captures[capture] = old;
+                        false
+                    }
+                })
+            }
+
+            Node::Sequence(nodes) => self.sequence(nodes, pos, captures, then),
+
+            Node::Alternatives(alternatives) => alternatives
+                .iter()
+                .any(|alternative| self.node(alternative, pos, captures, then)),
+
+            Node::Repeat {
+                inner,
+                min,
+                max,
+                greedy,
+            } => self.repeat(inner, (*min, *max, *greedy), 0, pos, captures, then),
+        }
+    }
+
+    fn sequence(&self, nodes: &[Node], pos: usize, captures: &mut Captures, then: Then) -> bool {
+        match nodes.split_first() {
+            Some((first, rest)) => self.node(first, pos, captures, &mut |pos, captures| {
+                self.sequence(rest, pos, captures, then)
+            }),
+            None => then(pos, captures),
+        }
+    }
+
+    fn repeat(
+        &self,
+        inner: &Node,
+        bounds: (usize, Option<usize>, bool),
+        count: usize,
+        pos: usize,
+        captures: &mut Captures,
+        then: Then,
+    ) -> bool {
+        let (min, max, greedy) = bounds;
+        let again = |captures: &mut Captures, then: Then| {
+            max.is_none_or(|max| count < max)
+                && self.node(inner, pos, captures, &mut |end, captures| {
+                    // Matching nothing over and over again would never end
+                    (end != pos || count < min)
+                        && self.repeat(inner, bounds, count + 1, end, captures, then)
+                })
+        };
+
+        if count < min {
+            return again(captures, then);
+        }
+
+        // Lazy repeats try stopping before trying to match again, greedy ones the other way round
+        if !greedy && then(pos, captures) {
+            return true;
+        }
+        again(captures, then) || (greedy && then(pos, captures))
+    }
+}
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            pattern: pattern.as_bytes(),
+            pos: 0,
+            captures: 0,
+            names: Vec::new(),
+        };
+
+        let root = parser.alternatives()?;
+        if parser.pos != pattern.len() {
+            return Err("unmatched ')'".to_owned());
+        }
+
+        Ok(Self {
+            root,
+            captures: parser.captures,
+            names: parser.names,
+        })
+    }
+
+    /// Find the leftmost match in the text
+    pub fn find(&self, text: &str) -> Option<Match> {
+        let matcher = Matcher {
+            text: text.as_bytes(),
+        };
+
+        for start in 0..=text.len() {
+            let mut captures = vec![None; self.captures];
+            let mut end = start;
+            let found = matcher.node(&self.root, start, &mut captures, &mut |pos, _| {
+                end = pos;
+                true
+            });
+
+            if found {
+                let named = self
+                    .names
+                    .iter()
+                    .filter_map(|(name, idx)| Some((name.clone(), captures[*idx].clone()?)))
+                    .collect();
+                return Some(Match {
+                    range: start..end,
+                    named,
+                });
+            }
+        }
+
+        None
+    }
+}
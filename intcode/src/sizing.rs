@@ -0,0 +1,151 @@
+use alloc::collections::BTreeMap;
+
+use crate::{
+    decode::{Instruction, Op},
+    Interpreter, Memory,
+};
+
+/// Memory which records the highest address used, keeping cells written beyond the end of the
+/// interpreter's memory on the side so that the program sees them as if memory were bigger
+#[derive(Default)]
+struct Tracker {
+    highest: Option<usize>,
+    beyond: BTreeMap<usize, i64>,
+}
+
+impl Tracker {
+    fn touch(&mut self, idx: usize) {
+        self.highest = self.highest.max(Some(idx));
+    }
+}
+
+impl Memory for Tracker {
+    fn read(&mut self, memory: &[i64], idx: usize) -> i64 {
+        self.touch(idx);
+        match memory.get(idx) {
+            Some(&value) => value,
+            None => self.beyond.get(&idx).copied().unwrap_or(0),
+        }
+    }
+
+    fn write(&mut self, memory: &mut [i64], idx: usize, value: i64) {
+        self.touch(idx);
+        match memory.get_mut(idx) {
+            Some(cell) => *cell = value,
+            None => {
+                self.beyond.insert(idx, value);
+            }
+        }
+    }
+}
+
+/// Enough room past the program counter for the longest instruction
+const LONGEST_INSTRUCTION: usize = 4;
+
+impl Interpreter {
+    /// How many cells of memory the program needs, found by a dry run on a copy of the
+    /// interpreter until it halts or needs more input, so it's only as good as the input queued
+    /// beforehand. This is at least the size of memory as it is, and resizing memory to it lets the
+    /// program run the same way without addressing memory out of bounds.
+    ///
+    /// Like `run`, this doesn't return if the program never stops.
+    pub fn required_memory(&self) -> usize {
+        self.dry_run(None)
+    }
+
+    /// Like `required_memory`, but whenever the program needs more input it's given the next
+    /// value from `input` and the dry run carries on until the program halts. Interactive programs
+    /// often don't use all of their memory until they've had some input, and feeding them
+    /// something plausible, like a robot seeing the same thing wherever it goes, shows what they
+    /// need.
+    pub fn required_memory_with(&self, mut input: impl FnMut() -> i64) -> usize {
+        self.dry_run(Some(&mut input))
+    }
+
+    fn dry_run(&self, mut input: Option<&mut dyn FnMut() -> i64>) -> usize {
+        let mut interpreter = self.clone();
+        let mut tracker = Tracker::default();
+
+        loop {
+            // Instructions are fetched straight from memory, so if the program jumps past the end
+            // then memory grows to take in what it's written there
+            let end = interpreter.pc + LONGEST_INSTRUCTION;
+            if end > interpreter.memory.len() {
+                let cells = tracker.beyond.keys().next_back().map_or(0, |&idx| idx + 1);
+                interpreter.memory.resize(end.max(cells), 0);
+                for (idx, value) in core::mem::take(&mut tracker.beyond) {
+                    interpreter.memory[idx] = value;
+                }
+            }
+
+            // The instruction itself only counts as far as it actually goes
+            let instruction = Instruction::decode(&interpreter.memory, interpreter.pc);
+            if let Some(instruction) = instruction {
+                tracker.touch(interpreter.pc + instruction.size() - 1);
+            }
+
+            if interpreter.step_on(&mut tracker) {
+                continue;
+            }
+
+            let waiting = instruction.is_some_and(|instruction| instruction.op == Op::Input)
+                && interpreter.input.is_empty();
+            match &mut input {
+                Some(input) if waiting => interpreter.input.push_back(input()),
+                _ => break,
+            }
+        }
+
+        self.memory
+            .len()
+            .max(tracker.highest.map_or(0, |idx| idx + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interpreter;
+    use alloc::vec;
+
+    #[test]
+    fn writes_past_the_end() {
+        let interpreter = Interpreter::new(vec![
+            1101, 1, 2, 50, // add 1, 2 -> [50]
+            109, 60, // arb 60
+            204, 5, // out [rb + 5]
+            99,
+        ]);
+        assert_eq!(interpreter.required_memory(), 66);
+
+        // Sizing memory to it lets the program run without going out of bounds
+        let mut interpreter = interpreter;
+        interpreter.memory.resize(interpreter.required_memory(), 0);
+        interpreter.run();
+        assert_eq!(interpreter.output, [0]);
+        assert_eq!(interpreter.memory[50], 3);
+
+        // Memory which is already bigger stays that size
+        interpreter.memory.resize(100, 0);
+        assert_eq!(interpreter.required_memory(), 100);
+    }
+
+    #[test]
+    fn missing_input() {
+        let mut interpreter = Interpreter::new(vec![
+            3, 7, // in -> [7]
+            1101, 1, 1, 80, // add 1, 1 -> [80]
+            99, 0,
+        ]);
+
+        // The dry run stops where the program wants input, before it gets to the addition
+        assert_eq!(interpreter.required_memory(), 8);
+        assert_eq!(interpreter.required_memory_with(|| 0), 81);
+
+        interpreter.input.push_back(5);
+        assert_eq!(interpreter.required_memory(), 81);
+
+        // None of which ran the program itself
+        assert_eq!(interpreter.pc, 0);
+        assert_eq!(interpreter.input, [5]);
+    }
+}
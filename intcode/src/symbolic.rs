@@ -0,0 +1,578 @@
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    fmt,
+    ops::{Add, Mul, Neg, Range, Sub},
+};
+
+use crate::decode::{Instruction, Mode, Op, Parameter};
+
+/// A linear combination of unknowns plus a constant. Unknowns are referred to by their index in
+/// `Executor::unknowns`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Expr {
+    pub constant: i64,
+
+    /// The coefficient of each unknown, none of which are zero
+    pub terms: BTreeMap<usize, i64>,
+}
+
+impl Expr {
+    pub fn unknown(idx: usize) -> Self {
+        let mut terms = BTreeMap::new();
+        terms.insert(idx, 1);
+        Self { constant: 0, terms }
+    }
+
+    /// The expression's value, if it doesn't depend on any unknowns
+    pub fn as_constant(&self) -> Option<i64> {
+        if self.terms.is_empty() {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    /// The expression's value given a value for every unknown
+    pub fn evaluate(&self, values: &[i64]) -> i64 {
+        self.terms
+            .iter()
+            .fold(self.constant, |acc, (&idx, &coefficient)| {
+                acc + coefficient * values[idx]
+            })
+    }
+
+    /// Write the expression out using the given unknowns' names
+    pub fn display<'a>(&'a self, unknowns: &'a [Unknown]) -> impl fmt::Display + 'a {
+        Formula {
+            expr: self,
+            unknowns,
+        }
+    }
+}
+
+impl From<i64> for Expr {
+    fn from(constant: i64) -> Self {
+        Self {
+            constant,
+            terms: BTreeMap::new(),
+        }
+    }
+}
+
+impl Add for Expr {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self.constant += other.constant;
+        for (idx, coefficient) in other.terms {
+            let sum = *self.terms.entry(idx).or_insert(0) + coefficient;
+            if sum == 0 {
+                self.terms.remove(&idx);
+            } else {
+                self.terms.insert(idx, sum);
+            }
+        }
+        self
+    }
+}
+
+impl Mul<i64> for Expr {
+    type Output = Self;
+
+    fn mul(mut self, factor: i64) -> Self {
+        if factor == 0 {
+            return Self::from(0);
+        }
+
+        self.constant *= factor;
+        self.terms
+            .values_mut()
+            .for_each(|coefficient| *coefficient *= factor);
+        self
+    }
+}
+
+impl Neg for Expr {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self * -1
+    }
+}
+
+impl Sub for Expr {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+struct Formula<'a> {
+    expr: &'a Expr,
+    unknowns: &'a [Unknown],
+}
+
+impl fmt::Display for Formula<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for (&idx, &coefficient) in &self.expr.terms {
+            let sign = if coefficient < 0 { "-" } else { "+" };
+            if first {
+                if coefficient < 0 {
+                    write!(f, "-")?;
+                }
+            } else {
+                write!(f, " {} ", sign)?;
+            }
+
+            if coefficient.abs() != 1 {
+                write!(f, "{} * ", coefficient.abs())?;
+            }
+            write!(f, "{}", self.unknowns[idx].name)?;
+            first = false;
+        }
+
+        if first {
+            write!(f, "{}", self.expr.constant)
+        } else if self.expr.constant < 0 {
+            write!(f, " - {}", -self.expr.constant)
+        } else if self.expr.constant > 0 {
+            write!(f, " + {}", self.expr.constant)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A value the executor solves for, along with the values it may take
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Unknown {
+    pub name: String,
+    pub range: Range<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    Zero,
+    NonZero,
+    Negative,
+    NonNegative,
+}
+
+/// Something that had to hold of the unknowns for execution to take the path it did
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Constraint {
+    pub expr: Expr,
+    pub relation: Relation,
+}
+
+impl Constraint {
+    pub fn holds(&self, values: &[i64]) -> bool {
+        let value = self.expr.evaluate(values);
+        match self.relation {
+            Relation::Zero => value == 0,
+            Relation::NonZero => value != 0,
+            Relation::Negative => value < 0,
+            Relation::NonNegative => value >= 0,
+        }
+    }
+}
+
+/// A concolic interpreter, which runs a program with concrete values for its unknowns while
+/// keeping track of every value as a linear expression of them.
+///
+/// Whenever execution depends on an unknown, be it through a comparison, a jump, an address or
+/// a multiplication of two unknowns, the concrete value is used and the condition that made it
+/// so is recorded in `constraints`. Any output is then exact for every assignment of the unknowns
+/// satisfying those constraints.
+#[derive(Debug, Clone)]
+pub struct Executor {
+    pub memory: Vec<Expr>,
+
+    pub input: VecDeque<Expr>,
+    pub output: VecDeque<Expr>,
+
+    pub pc: usize,
+
+    pub done: bool,
+
+    pub relative_base: i64,
+
+    pub unknowns: Vec<Unknown>,
+
+    /// The concrete value of each unknown for this run
+    pub values: Vec<i64>,
+
+    pub constraints: Vec<Constraint>,
+}
+
+impl Executor {
+    pub fn new(memory: &[i64]) -> Self {
+        Self {
+            memory: memory.iter().map(|&value| Expr::from(value)).collect(),
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            pc: 0,
+            done: false,
+            relative_base: 0,
+            unknowns: Vec::new(),
+            values: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    /// Introduce a new unknown, initially taking the lowest value in its range
+    pub fn unknown(&mut self, name: &str, range: Range<i64>) -> Expr {
+        self.values.push(range.start);
+        self.unknowns.push(Unknown {
+            name: name.to_owned(),
+            range,
+        });
+        Expr::unknown(self.unknowns.len() - 1)
+    }
+
+    /// Replace the contents of a memory cell with a new unknown
+    pub fn unknown_cell(&mut self, idx: usize, name: &str, range: Range<i64>) {
+        self.memory[idx] = self.unknown(name, range);
+    }
+
+    /// Add a new unknown to the input
+    pub fn unknown_input(&mut self, name: &str, range: Range<i64>) {
+        let unknown = self.unknown(name, range);
+        self.input.push_back(unknown);
+    }
+
+    /// The concrete value of an expression in this run, recording that it must keep that value
+    fn concretize(&mut self, expr: Expr) -> i64 {
+        let value = expr.evaluate(&self.values);
+        self.constrain(expr - Expr::from(value), Relation::Zero);
+        value
+    }
+
+    /// Record that an expression satisfies a relation, unless that's trivially true
+    fn constrain(&mut self, expr: Expr, relation: Relation) {
+        if expr.as_constant().is_none() {
+            self.constraints.push(Constraint { expr, relation });
+        }
+    }
+
+    /// The address the given parameter of the current instruction refers to
+    fn address(&mut self, i: usize, param: Parameter) -> usize {
+        let word = self.memory[self.pc + 1 + i].clone();
+        let base = match param.mode {
+            Mode::Relative => self.relative_base,
+            _ => 0,
+        };
+        (base + self.concretize(word)) as usize
+    }
+
+    /// The value of the given parameter of the current instruction. Immediate parameters keep
+    /// their expression, but addresses have to be concrete.
+    fn load(&mut self, i: usize, param: Parameter) -> Expr {
+        match param.mode {
+            Mode::Immediate => self.memory[self.pc + 1 + i].clone(),
+            _ => {
+                let idx = self.address(i, param);
+                self.memory[idx].clone()
+            }
+        }
+    }
+
+    fn store(&mut self, i: usize, param: Parameter, value: Expr) {
+        let idx = self.address(i, param);
+        self.memory[idx] = value;
+    }
+
+    /// Whether a condition holds in this run, recording that it must keep doing so
+    fn branch(&mut self, expr: Expr, holds: Relation, fails: Relation) -> bool {
+        let taken = Constraint {
+            expr: expr.clone(),
+            relation: holds,
+        }
+        .holds(&self.values);
+        self.constrain(expr, if taken { holds } else { fails });
+        taken
+    }
+
+    /// Execute a single instruction, returning whether one was executed rather than the program
+    /// halting or waiting for input
+    pub fn step(&mut self) -> bool {
+        // The opcode decides what happens next so it has to be concrete, but parameters only need
+        // to be if they're used as addresses
+        let opcode = self.memory[self.pc].clone();
+        let opcode = self.concretize(opcode);
+        let words: Vec<_> = std::iter::once(opcode)
+            .chain(
+                self.memory[self.pc + 1..self.memory.len().min(self.pc + 4)]
+                    .iter()
+                    .map(|word| word.evaluate(&self.values)),
+            )
+            .collect();
+
+        let instruction = Instruction::decode(&words, 0).unwrap_or_else(|| {
+            panic!(
+                "invalid instruction {:?} at address {}",
+                words.first(),
+                self.pc
+            )
+        });
+        let params = instruction.params();
+        let next = self.pc + instruction.size();
+
+        match instruction.op {
+            Op::Add => {
+                let value = self.load(0, params[0]) + self.load(1, params[1]);
+                self.store(2, params[2], value);
+            }
+
+            Op::Mul => {
+                let (a, b) = (self.load(0, params[0]), self.load(1, params[1]));
+                let value = match (a.as_constant(), b.as_constant()) {
+                    (Some(a), _) => b * a,
+                    (_, Some(b)) => a * b,
+                    (None, None) => {
+                        let b = self.concretize(b);
+                        a * b
+                    }
+                };
+                self.store(2, params[2], value);
+            }
+
+            Op::LessThan => {
+                let difference = self.load(0, params[0]) - self.load(1, params[1]);
+                let value = self.branch(difference, Relation::Negative, Relation::NonNegative);
+                self.store(2, params[2], Expr::from(value as i64));
+            }
+
+            Op::Equals => {
+                let difference = self.load(0, params[0]) - self.load(1, params[1]);
+                let value = self.branch(difference, Relation::Zero, Relation::NonZero);
+                self.store(2, params[2], Expr::from(value as i64));
+            }
+
+            Op::Input => match self.input.pop_front() {
+                Some(input) => self.store(0, params[0], input),
+                None => return false,
+            },
+
+            Op::Output => {
+                let value = self.load(0, params[0]);
+                self.output.push_back(value);
+            }
+
+            Op::JumpIfTrue | Op::JumpIfFalse => {
+                let condition = self.load(0, params[0]);
+                let (holds, fails) = if instruction.op == Op::JumpIfTrue {
+                    (Relation::NonZero, Relation::Zero)
+                } else {
+                    (Relation::Zero, Relation::NonZero)
+                };
+
+                if self.branch(condition, holds, fails) {
+                    let target = self.load(1, params[1]);
+                    self.pc = self.concretize(target) as usize;
+                    return true;
+                }
+            }
+
+            Op::AdjustBase => {
+                let offset = self.load(0, params[0]);
+                self.relative_base += self.concretize(offset);
+            }
+
+            Op::Halt => {
+                self.done = true;
+                return false;
+            }
+        }
+
+        self.pc = next;
+        true
+    }
+
+    /// Run the program until it halts or needs more input
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
+
+    /// Find values for the unknowns for which the expression picked out of the executor after
+    /// running it from this state evaluates to `target`.
+    ///
+    /// Each run gives a formula which only holds along the path it took, so solutions of the
+    /// formula off that path are used to explore other paths until one whose own formula can be
+    /// solved along it is found.
+    pub fn solve(&self, target: i64, pick: impl Fn(&Self) -> Expr) -> Option<Vec<i64>> {
+        let mut values = self.values.clone();
+        let mut tried = HashSet::new();
+
+        while tried.insert(values.clone()) {
+            let mut executor = self.clone();
+            executor.values = values.clone();
+            executor.run();
+
+            let expr = pick(&executor);
+            let on_path = self.search(&expr, target, &values, |candidate| {
+                executor
+                    .constraints
+                    .iter()
+                    .all(|constraint| constraint.holds(candidate))
+            });
+            if on_path.is_some() {
+                return on_path;
+            }
+
+            values = self.search(&expr, target, &values, |candidate| {
+                !tried.contains(candidate)
+            })?;
+        }
+
+        None
+    }
+
+    /// Search every value of the unknowns in `expr` within their ranges, keeping the rest as in
+    /// `values`, for one where it evaluates to `target` and which is accepted
+    fn search(
+        &self,
+        expr: &Expr,
+        target: i64,
+        values: &[i64],
+        mut accept: impl FnMut(&[i64]) -> bool,
+    ) -> Option<Vec<i64>> {
+        let mut candidate = values.to_vec();
+
+        // The last unknown is solved for, every other one is enumerated
+        let (&last, &coefficient) = match expr.terms.iter().next_back() {
+            Some(term) => term,
+            None if expr.constant == target && accept(&candidate) => return Some(candidate),
+            None => return None,
+        };
+        let enumerated: Vec<_> = expr
+            .terms
+            .keys()
+            .copied()
+            .filter(|&idx| idx != last)
+            .collect();
+
+        for &idx in &enumerated {
+            let range = &self.unknowns[idx].range;
+            if range.start >= range.end {
+                return None;
+            }
+            candidate[idx] = range.start;
+        }
+
+        loop {
+            candidate[last] = 0;
+            let rest = target - expr.evaluate(&candidate);
+            if rest % coefficient == 0 {
+                candidate[last] = rest / coefficient;
+                if self.unknowns[last].range.contains(&candidate[last]) && accept(&candidate) {
+                    return Some(candidate);
+                }
+            }
+
+            // Move on to the next combination of enumerated unknowns, like an odometer
+            let mut carried = true;
+            for &idx in &enumerated {
+                let range = &self.unknowns[idx].range;
+                candidate[idx] += 1;
+                if candidate[idx] < range.end {
+                    carried = false;
+                    break;
+                }
+                candidate[idx] = range.start;
+            }
+
+            if carried {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Interpreter;
+
+    /// Outputs `a * 2` if `a` is at least 3, and `a + b` otherwise
+    const BRANCHING: [i64; 34] = [
+        1007, 30, 3, 32, // lt [30], 3 -> [32]
+        1005, 32, 14, // jnz [32], 14
+        1002, 30, 2, 33, // mul [30], 2 -> [33]
+        4, 33, // out [33]
+        99, //
+        1, 30, 31, 33, // add [30], [31] -> [33]
+        1105, 1, 11, // jnz 1, 11
+        0, 0, 0, 0, 0, 0, 0, 0, 0, //
+        0, 0, 0, 0, // a, b, the comparison and the result
+    ];
+
+    fn branching() -> Executor {
+        let mut executor = Executor::new(&BRANCHING);
+        executor.unknown_cell(30, "a", 0..10);
+        executor.unknown_cell(31, "b", 0..3);
+        executor
+    }
+
+    /// What the interpreter outputs for the program with the given values for `a` and `b`
+    fn concrete(a: i64, b: i64) -> i64 {
+        let mut interpreter = Interpreter::new(BRANCHING.to_vec());
+        interpreter.memory[30] = a;
+        interpreter.memory[31] = b;
+        while interpreter.step() {}
+        interpreter.output[0]
+    }
+
+    #[test]
+    fn solve_on_first_path() {
+        let values = branching().solve(4, |executor| executor.output[0].clone());
+        let values = values.unwrap();
+        assert!(values[0] < 3);
+        assert_eq!(concrete(values[0], values[1]), 4);
+    }
+
+    #[test]
+    fn solve_on_other_path() {
+        // The first run has `a` at 0, so takes the path adding `b`, whose formula only gives 8 for
+        // values of `a` which take the other path
+        let values = branching().solve(8, |executor| executor.output[0].clone());
+        assert_eq!(values, Some(vec![4, 2]));
+        assert_eq!(concrete(4, 2), 8);
+    }
+
+    #[test]
+    fn unsolvable() {
+        // Neither `a + b` for `a` under 3 nor `a * 2` can make 5
+        assert_eq!(
+            branching().solve(5, |executor| executor.output[0].clone()),
+            None
+        );
+    }
+
+    #[test]
+    fn formula() {
+        let mut executor = Executor::new(&[
+            1002, 9, 3, 9, // mul [9], 3 -> [9]
+            1, 9, 10, 0, // add [9], [10] -> [0]
+            99, 0, 0,
+        ]);
+        executor.unknown_cell(9, "noun", 0..100);
+        executor.unknown_cell(10, "verb", 0..100);
+        executor.run();
+
+        assert_eq!(
+            executor.memory[0].display(&executor.unknowns).to_string(),
+            "3 * noun + verb"
+        );
+        assert!(executor.constraints.is_empty());
+
+        let expr = Expr::from(-7) - Expr::unknown(0) + Expr::unknown(1) * 2;
+        assert_eq!(
+            expr.display(&executor.unknowns).to_string(),
+            "-noun + 2 * verb - 7"
+        );
+        assert_eq!(Expr::from(5).display(&[]).to_string(), "5");
+    }
+}
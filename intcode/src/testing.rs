@@ -0,0 +1,73 @@
+//! Programs and drivers shared by the tests checking the execution engines against the
+//! interpreter. Not part of the crate's API.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{
+    extension::{Effect, Extension},
+    Interpreter,
+};
+
+/// Something which can run an intcode program until it halts or needs more input
+pub trait Machine {
+    fn resume(&mut self);
+
+    fn interpreter(&mut self) -> &mut Interpreter;
+}
+
+impl Machine for Interpreter {
+    // Stepping instead of running keeps debug builds from overflowing the stack
+    fn resume(&mut self) {
+        while self.step() {}
+    }
+
+    fn interpreter(&mut self) -> &mut Interpreter {
+        self
+    }
+}
+
+/// Play day13's breakout by following the ball with the paddle, returning everything the game
+/// drew
+pub fn breakout(machine: &mut impl Machine) -> Vec<i64> {
+    let mut drawn = Vec::new();
+    let mut p_x = 21;
+    let mut b_x = p_x - 2;
+
+    while !machine.interpreter().done {
+        machine.interpreter().input.push_back(if b_x < p_x {
+            p_x -= 1;
+            -1
+        } else if b_x != p_x {
+            p_x += 1;
+            1
+        } else {
+            0
+        });
+
+        machine.resume();
+
+        let output = &mut machine.interpreter().output;
+        while !output.is_empty() {
+            let tile: Vec<_> = output.drain(..3).collect();
+            if tile[2] == 4 {
+                b_x = tile[0];
+            }
+            drawn.extend(tile);
+        }
+    }
+
+    drawn
+}
+
+/// An extension opcode storing 42 the first time it runs and halting the second
+pub fn store_then_halt() -> Extension {
+    let stored = AtomicBool::new(false);
+    Extension::storing(50, 0, move |_, _| {
+        if stored.swap(true, Ordering::Relaxed) {
+            Effect::Halt(0)
+        } else {
+            Effect::Store(42)
+        }
+    })
+}
@@ -0,0 +1,475 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use crate::{
+    decode::{Instruction, Mode, Op, Parameter},
+    Interpreter, Watched,
+};
+
+/// What happened when transpiled code handed an instruction it couldn't run natively over to the
+/// interpreter
+#[doc(hidden)]
+pub enum Fallback {
+    Executed,
+
+    /// The instruction wrote into an address baked into native code
+    Overwrote,
+
+    /// The program halted or is waiting for input
+    Stopped,
+}
+
+/// Execute a single instruction on behalf of transpiled code, checking whether it overwrote any of
+/// the given baked addresses
+#[doc(hidden)]
+pub fn fallback(interpreter: &mut Interpreter, baked: &[bool]) -> Fallback {
+    let mut memory = Watched::new(baked);
+    if !interpreter.step_on(&mut memory) {
+        return Fallback::Stopped;
+    }
+
+    match memory.overwrote {
+        Some(_) => Fallback::Overwrote,
+        None => Fallback::Executed,
+    }
+}
+
+/// Whether the straight-line code leading up to the jump at `pc` mentions the address after it as
+/// an immediate, which is how calls push their return address
+fn pushes_return(reachable: &BTreeMap<usize, Instruction>, pc: usize) -> bool {
+    let next = pc as i64 + 3;
+    let mut start = pc;
+    for (&at, instruction) in reachable.range(..pc).rev() {
+        let jumps = matches!(instruction.op, Op::JumpIfTrue | Op::JumpIfFalse | Op::Halt);
+        if at + instruction.size() != start || jumps {
+            break;
+        }
+
+        let mentions = instruction
+            .params()
+            .iter()
+            .any(|param| param.mode == Mode::Immediate && param.value == next);
+        if mentions {
+            return true;
+        }
+
+        start = at;
+    }
+
+    false
+}
+
+/// What the transpiler knows about a program before generating any code
+struct Analysis {
+    /// Every instruction reachable from the start of the program which gets native code
+    compiled: BTreeMap<usize, Instruction>,
+
+    /// Whether the program stores into each address through a position mode parameter
+    patched: Vec<bool>,
+
+    /// Whether each address holds a value the native code relies on not changing
+    baked: Vec<bool>,
+}
+
+impl Analysis {
+    fn new(image: &[i64]) -> Self {
+        // Follow every path through the program from its start. The address after an
+        // unconditional jump is only reachable if the jump is a call that returns there.
+        let mut reachable = BTreeMap::new();
+        let mut calls = Vec::new();
+        let mut pending = vec![0];
+        while !pending.is_empty() {
+            while let Some(pc) = pending.pop() {
+                if reachable.contains_key(&pc) {
+                    continue;
+                }
+
+                let instruction = match Instruction::decode(image, pc) {
+                    Some(instruction) => instruction,
+                    None => continue,
+                };
+                reachable.insert(pc, instruction);
+
+                let next = pc + instruction.size();
+                match instruction.op {
+                    Op::Halt => {}
+
+                    Op::JumpIfTrue | Op::JumpIfFalse => {
+                        let [condition, target] =
+                            [instruction.params()[0], instruction.params()[1]];
+                        if target.mode == Mode::Immediate && target.value >= 0 {
+                            pending.push(target.value as usize);
+                        }
+
+                        let always = condition.mode == Mode::Immediate
+                            && (condition.value != 0) == (instruction.op == Op::JumpIfTrue);
+                        if always {
+                            calls.push(pc);
+                        } else {
+                            pending.push(next);
+                        }
+                    }
+
+                    _ => pending.push(next),
+                }
+            }
+
+            pending.extend(calls.iter().filter_map(|&pc| {
+                let next = pc + 3;
+                if !reachable.contains_key(&next) && pushes_return(&reachable, pc) {
+                    Some(next)
+                } else {
+                    None
+                }
+            }));
+        }
+
+        let mut patched = vec![false; image.len()];
+        for instruction in reachable.values() {
+            if let Some(i) = instruction.op.output_parameter() {
+                let param = instruction.params()[i];
+                if param.mode == Mode::Position && (param.value as usize) < image.len() {
+                    patched[param.value as usize] = true;
+                }
+            }
+        }
+
+        // Instructions whose opcode gets overwritten are left to the interpreter, and so are ones
+        // with negative addresses as the interpreter would crash on those anyway
+        let compiled: BTreeMap<_, _> = reachable
+            .into_iter()
+            .filter(|&(pc, instruction)| {
+                !patched[pc]
+                    && instruction.params().iter().enumerate().all(|(i, param)| {
+                        param.mode != Mode::Position || param.value >= 0 || patched[pc + 1 + i]
+                    })
+            })
+            .collect();
+
+        let mut baked = vec![false; image.len()];
+        for (&pc, instruction) in &compiled {
+            baked[pc] = true;
+            let params = pc + 1..pc + instruction.size();
+            for (baked, &patched) in baked[params.clone()].iter_mut().zip(&patched[params]) {
+                *baked = !patched;
+            }
+        }
+
+        Self {
+            compiled,
+            patched,
+            baked,
+        }
+    }
+
+    /// An expression evaluating to the value of the given parameter of the instruction at `pc`
+    fn load(&self, pc: usize, i: usize, param: Parameter) -> String {
+        let cell = pc + 1 + i;
+        if self.patched[cell] {
+            match param.mode {
+                Mode::Position => format!("it.memory[it.memory[{}] as usize]", cell),
+                Mode::Immediate => format!("it.memory[{}]", cell),
+                Mode::Relative => format!("it.memory[(rb + it.memory[{}]) as usize]", cell),
+            }
+        } else {
+            match param.mode {
+                Mode::Position => format!("it.memory[{}]", param.value),
+                Mode::Immediate => format!("{}i64", param.value),
+                Mode::Relative => format!("it.memory[(rb + {}) as usize]", param.value),
+            }
+        }
+    }
+
+    /// Statements storing `value` through the given parameter of the instruction at `pc`, which
+    /// hand over to the interpreter if they overwrite baked code
+    fn store(&self, pc: usize, i: usize, param: Parameter, next: usize, indent: &str) -> String {
+        let cell = pc + 1 + i;
+        let address = match (param.mode, self.patched[cell]) {
+            (Mode::Position, false) => {
+                // Every address stored into like this is patched, so never baked
+                return format!("{}it.memory[{}] = value;\n", indent, param.value);
+            }
+
+            (Mode::Relative, false) => format!("(rb + {}) as usize", param.value),
+            (Mode::Relative, true) => format!("(rb + it.memory[{}]) as usize", cell),
+            (_, true) => format!("it.memory[{}] as usize", cell),
+            (Mode::Immediate, false) => unreachable!("decoder accepted an immediate output"),
+        };
+
+        let mut out = String::new();
+        writeln!(out, "{}let idx = {};", indent, address).unwrap();
+        writeln!(out, "{}it.memory[idx] = value;", indent).unwrap();
+        writeln!(out, "{}if BAKED.get(idx) == Some(&true) {{", indent).unwrap();
+        writeln!(out, "{}    it.pc = {};", indent, next).unwrap();
+        writeln!(out, "{}    it.relative_base = rb;", indent).unwrap();
+        writeln!(out, "{}    return self.stale::<SINGLE>();", indent).unwrap();
+        writeln!(out, "{}}}", indent).unwrap();
+        out
+    }
+
+    /// The match arm executing the instruction at `pc`
+    fn arm(&self, pc: usize, instruction: &Instruction) -> String {
+        const INDENT: &str = "                    ";
+
+        let params = instruction.params();
+        let next = pc + instruction.size();
+        let load = |i: usize| self.load(pc, i, params[i]);
+
+        let mut out = String::new();
+        writeln!(out, "                {} => {{", pc).unwrap();
+
+        match instruction.op {
+            Op::Add | Op::Mul | Op::LessThan | Op::Equals => {
+                let value = match instruction.op {
+                    Op::Add => format!("{} + {}", load(0), load(1)),
+                    Op::Mul => format!("{} * {}", load(0), load(1)),
+                    Op::LessThan => format!("({} < {}) as i64", load(0), load(1)),
+                    _ => format!("({} == {}) as i64", load(0), load(1)),
+                };
+                writeln!(out, "{}let value = {};", INDENT, value).unwrap();
+                out += &self.store(pc, 2, params[2], next, INDENT);
+                writeln!(out, "{}pc = {};", INDENT, next).unwrap();
+            }
+
+            Op::Input => {
+                writeln!(out, "{}let value = match it.input.pop_front() {{", INDENT).unwrap();
+                writeln!(out, "{}    Some(value) => value,", INDENT).unwrap();
+                writeln!(out, "{}    None => {{", INDENT).unwrap();
+                writeln!(out, "{}        it.pc = {};", INDENT, pc).unwrap();
+                writeln!(out, "{}        it.relative_base = rb;", INDENT).unwrap();
+                writeln!(out, "{}        return false;", INDENT).unwrap();
+                writeln!(out, "{}    }}", INDENT).unwrap();
+                writeln!(out, "{}}};", INDENT).unwrap();
+                out += &self.store(pc, 0, params[0], next, INDENT);
+                writeln!(out, "{}pc = {};", INDENT, next).unwrap();
+            }
+
+            Op::Output => {
+                writeln!(out, "{}let value = {};", INDENT, load(0)).unwrap();
+                writeln!(out, "{}it.output.push_back(value);", INDENT).unwrap();
+                writeln!(out, "{}pc = {};", INDENT, next).unwrap();
+            }
+
+            Op::JumpIfTrue | Op::JumpIfFalse => {
+                let comparison = if instruction.op == Op::JumpIfTrue {
+                    "!="
+                } else {
+                    "=="
+                };
+                writeln!(
+                    out,
+                    "{}pc = if {} {} 0 {{ {} as usize }} else {{ {} }};",
+                    INDENT,
+                    load(0),
+                    comparison,
+                    load(1),
+                    next
+                )
+                .unwrap();
+            }
+
+            Op::AdjustBase => {
+                writeln!(out, "{}rb += {};", INDENT, load(0)).unwrap();
+                writeln!(out, "{}pc = {};", INDENT, next).unwrap();
+            }
+
+            Op::Halt => {
+                writeln!(out, "{}it.pc = {};", INDENT, pc).unwrap();
+                writeln!(out, "{}it.relative_base = rb;", INDENT).unwrap();
+                writeln!(out, "{}it.done = true;", INDENT).unwrap();
+                writeln!(out, "{}return false;", INDENT).unwrap();
+            }
+        }
+
+        writeln!(out, "                }}").unwrap();
+        out
+    }
+}
+
+/// Write out the items of a static array, a handful per line
+fn array<T: ToString>(out: &mut String, items: impl Iterator<Item = T>) {
+    let items: Vec<_> = items.map(|item| item.to_string()).collect();
+    for line in items.chunks(16) {
+        writeln!(out, "    {},", line.join(", ")).unwrap();
+    }
+}
+
+/// Translate a program image into the source of a standalone Rust module. The module exposes a
+/// `Program` type which wraps an `Interpreter` and dereferences to it, so it's fed input and read
+/// output from in exactly the same way, but whose `run` and `step` methods execute native code.
+///
+/// Every instruction reachable from the start of the image gets compiled, reading parameters the
+/// program stores into directly from memory. Anything else, such as instructions whose opcode gets
+/// overwritten or code reached only through computed jumps, is executed one instruction at a time
+/// by the interpreter. If the program ever overwrites any other part of the compiled code, or was
+/// handed an interpreter whose memory doesn't match the image, it's run entirely by the
+/// interpreter from then on.
+pub fn transpile(image: &[i64]) -> String {
+    let analysis = Analysis::new(image);
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// Generated by intcode-transpile from a {} word program, do not edit.",
+        image.len()
+    )
+    .unwrap();
+    out += r#"
+use core::ops::{Deref, DerefMut};
+
+use intcode::{
+    transpile::{fallback, Fallback},
+    Interpreter,
+};
+
+"#;
+
+    writeln!(
+        out,
+        "/// The program image the native code was generated from"
+    )
+    .unwrap();
+    writeln!(out, "pub static IMAGE: [i64; {}] = [", image.len()).unwrap();
+    array(&mut out, image.iter());
+    writeln!(out, "];").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(
+        out,
+        "/// Whether each address of the image is baked into the native code"
+    )
+    .unwrap();
+    writeln!(out, "static BAKED: [bool; {}] = [", image.len()).unwrap();
+    array(&mut out, analysis.baked.iter());
+    writeln!(out, "];").unwrap();
+
+    out += r#"
+#[derive(Debug, Clone)]
+pub struct Program {
+    interpreter: Interpreter,
+
+    /// Whether the native code still matches the program in memory
+    native: bool,
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Self::from_interpreter(Interpreter::new(IMAGE.to_vec()))
+    }
+
+    /// Take over an interpreter for the same program. Changes made to its memory beforehand
+    /// are noticed, but any made directly through `DerefMut` afterwards aren't.
+    pub fn from_interpreter(interpreter: Interpreter) -> Self {
+        let native = IMAGE
+            .iter()
+            .zip(&BAKED)
+            .enumerate()
+            .all(|(idx, (value, &baked))| !baked || interpreter.memory.get(idx) == Some(value));
+
+        Self {
+            interpreter,
+            native,
+        }
+    }
+
+    pub fn into_interpreter(self) -> Interpreter {
+        self.interpreter
+    }
+
+    /// Whether the program is still being run as native code
+    pub fn is_native(&self) -> bool {
+        self.native
+    }
+
+    /// Run the program until it halts or needs more input, exactly like `Interpreter::run`
+    pub fn run(&mut self) {
+        self.execute::<false>();
+    }
+
+    /// Execute a single instruction, exactly like `Interpreter::step`
+    pub fn step(&mut self) -> bool {
+        self.execute::<true>()
+    }
+
+    /// Carry on with the interpreter once the native code has been overwritten
+    fn stale<const SINGLE: bool>(&mut self) -> bool {
+        self.native = false;
+        if !SINGLE {
+            self.interpreter.run();
+        }
+        true
+    }
+
+    fn execute<const SINGLE: bool>(&mut self) -> bool {
+        if !self.native {
+            return if SINGLE {
+                self.interpreter.step()
+            } else {
+                self.interpreter.run();
+                false
+            };
+        }
+
+        let it = &mut self.interpreter;
+        let mut pc = it.pc;
+        let mut rb = it.relative_base;
+
+        loop {
+            match pc {
+"#;
+
+    for (&pc, instruction) in &analysis.compiled {
+        out += &analysis.arm(pc, instruction);
+    }
+
+    out += r#"                _ => {
+                    it.pc = pc;
+                    it.relative_base = rb;
+                    match fallback(it, &BAKED) {
+                        Fallback::Executed => {}
+                        Fallback::Overwrote => return self.stale::<SINGLE>(),
+                        Fallback::Stopped => return false,
+                    }
+                    pc = it.pc;
+                    rb = it.relative_base;
+                }
+            }
+
+            if SINGLE {
+                it.pc = pc;
+                it.relative_base = rb;
+                return true;
+            }
+        }
+    }
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Program {
+    type Target = Interpreter;
+
+    fn deref(&self) -> &Interpreter {
+        &self.interpreter
+    }
+}
+
+impl DerefMut for Program {
+    fn deref_mut(&mut self) -> &mut Interpreter {
+        &mut self.interpreter
+    }
+}
+"#;
+
+    out
+}
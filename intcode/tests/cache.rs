@@ -0,0 +1,146 @@
+use std::thread;
+
+use intcode::{
+    testing::{self, store_then_halt, Machine},
+    BlockCache, Interpreter, STACK_SIZE,
+};
+
+/// Run the test on a thread with room for `Interpreter::run` to recurse
+fn deep(test: impl FnOnce() + Send + 'static) {
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(test)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+/// Check two interpreters are in exactly the same state
+fn assert_same(cached: &Interpreter, expected: &Interpreter) {
+    assert_eq!(cached.memory, expected.memory);
+    assert_eq!(cached.input, expected.input);
+    assert_eq!(cached.output, expected.output);
+    assert_eq!(cached.pc, expected.pc);
+    assert_eq!(cached.done, expected.done);
+    assert_eq!(cached.relative_base, expected.relative_base);
+}
+
+/// Run the program until it halts or needs more input both with `Interpreter::run` and through a
+/// block cache, checking they end up in exactly the same state
+fn differential(interpreter: &Interpreter) -> Interpreter {
+    let mut expected = interpreter.clone();
+    expected.run();
+
+    let mut cached = interpreter.clone();
+    BlockCache::new().run(&mut cached);
+
+    assert_same(&cached, &expected);
+    cached
+}
+
+/// An interpreter run through a block cache which is kept between runs
+struct Cached {
+    cache: BlockCache,
+    interpreter: Interpreter,
+}
+
+impl Machine for Cached {
+    fn resume(&mut self) {
+        self.cache.run(&mut self.interpreter);
+    }
+
+    fn interpreter(&mut self) -> &mut Interpreter {
+        &mut self.interpreter
+    }
+}
+
+#[test]
+fn diagnostics() {
+    deep(|| {
+        for &system in &[1, 5] {
+            let mut interpreter = Interpreter::from_input(include_str!("../../inputs/day05.txt"));
+            interpreter.input.push_back(system);
+            assert!(differential(&interpreter).done);
+        }
+    });
+}
+
+#[test]
+fn boost() {
+    deep(|| {
+        for &mode in &[1, 2] {
+            let mut interpreter = Interpreter::from_input(include_str!("../../inputs/day09.txt"));
+            interpreter.memory.resize(1080, 0);
+            interpreter.input.push_back(mode);
+            assert_eq!(differential(&interpreter).output.len(), 1);
+        }
+    });
+}
+
+#[test]
+fn breakout() {
+    deep(|| {
+        let mut interpreter = Interpreter::from_input(include_str!("../../inputs/day13.txt"));
+        interpreter.memory.extend_from_slice(&[0; 14]);
+        differential(&interpreter);
+
+        // Playing runs the program a little at a time, with the cache kept between runs
+        interpreter.memory[0] = 2;
+        let mut expected = interpreter.clone();
+        let mut cached = Cached {
+            cache: BlockCache::new(),
+            interpreter,
+        };
+        assert_eq!(
+            testing::breakout(&mut cached),
+            testing::breakout(&mut expected)
+        );
+        assert_same(&cached.interpreter, &expected);
+    });
+}
+
+#[test]
+fn self_modification() {
+    // Turns its own addition into a multiplication through a relative address
+    let interpreter = Interpreter::new(vec![
+        109, 9, // arb 9
+        21101, 1102, 0, 0, // add 1102, 0 -> [rb]
+        1105, 1, 9, // jnz 1, 9
+        1101, 3, 4, 17, // add 3, 4 -> [17]
+        4, 17, // out [17]
+        99, 0, 0,
+    ]);
+    assert_eq!(differential(&interpreter).output, [12]);
+
+    // Sums five values by patching the address its addition reads from each time round the loop
+    let interpreter = Interpreter::new(vec![
+        1, 20, 30, 30, // add [20], [30] -> [30]
+        1001, 1, 1, 1, // add [1], 1 -> [1]
+        1007, 1, 25, 31, // lt [1], 25 -> [31]
+        1005, 31, 0, // jnz [31], 0
+        4, 30, // out [30]
+        99, 0, 0, //
+        3, 1, 4, 1, 5, // the values
+        0, 0, 0, 0, 0, 0, 0,
+    ]);
+    assert_eq!(differential(&interpreter).output, [14]);
+}
+
+#[test]
+fn extensions_storing_into_code() {
+    // The extension overwrites the first instruction's parameter, which has been compiled
+    let program = vec![
+        104, 1, // out 1
+        50, 1, // ext -> [1]
+        6, 9, 10, // jz [9], [10]
+        0, 0, 0, 0,
+    ];
+
+    let mut expected = Interpreter::new(program.clone()).with_extensions(vec![store_then_halt()]);
+    expected.run();
+    assert_eq!(expected.output, [1, 42]);
+
+    let mut cached = Interpreter::new(program).with_extensions(vec![store_then_halt()]);
+    BlockCache::new().run(&mut cached);
+    assert_same(&cached, &expected);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "std")]
+
+use intcode::{
+    script::{Error, Script},
+    Interpreter,
+};
+
+fn droid() -> Interpreter {
+    let mut interpreter = Interpreter::from_input(include_str!("../../inputs/day25.txt"));
+    interpreter.memory.extend_from_slice(&[0; 225]);
+    interpreter
+}
+
+#[test]
+fn route_reaches_cockpit() {
+    let script = Script::parse(include_str!("../../day25/route.txt")).unwrap();
+    let variables = script.run(&mut droid()).unwrap();
+    assert_eq!(variables["code"], "100667393");
+}
+
+#[test]
+fn branches_on_weight() {
+    let script = Script::parse(
+        "
+        expect Command\\?
+        send south
+        send east
+        send south
+        send west
+        if Droids on this ship are (?<weight>heavier|lighter) goto weighed
+        fail the droid wasn't weighed
+        weighed:
+        fail other droids are ${weight}
+        ",
+    )
+    .unwrap();
+
+    match script.run(&mut droid()) {
+        Err(Error::Failed { line: 10, message }) => assert_eq!(message, "other droids are heavier"),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn rejects_unknown_labels() {
+    assert!(matches!(
+        Script::parse("goto nowhere"),
+        Err(Error::Parse { line: 1, .. })
+    ));
+}
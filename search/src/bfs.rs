@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use crate::{Node, Path, Tree};
+
+/// Search outwards from the starts a step at a time until every reachable node's been reached,
+/// stopping early at the first node for which `goal` returns true
+fn search<N: Node, I: IntoIterator<Item = N>>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Tree<N, usize>, Option<usize>) {
+    let mut tree = Tree::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        queue.extend(tree.reach(start, 0, None));
+    }
+
+    while let Some(idx) = queue.pop_front() {
+        if goal(tree.node(idx)) {
+            return (tree, Some(idx));
+        }
+
+        // Every node's reached in as few steps as possible the first time it's reached, so the
+        // tree only ever takes in new ones
+        let distance = tree.cost_at(idx) + 1;
+        for neighbor in neighbors(tree.node(idx)) {
+            queue.extend(tree.reach(neighbor, distance, Some(idx)));
+        }
+    }
+
+    (tree, None)
+}
+
+/// Find the fewest steps from any of the starts to a node for which `goal` returns true, moving
+/// from each node to its neighbors
+pub fn bfs<N: Node, I: IntoIterator<Item = N>>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>> {
+    let (tree, found) = search(starts, neighbors, goal);
+    found.map(|idx| tree.path_to(idx))
+}
+
+/// Find the fewest steps from any of the starts to every node reachable from them
+pub fn bfs_all<N: Node, I: IntoIterator<Item = N>>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+) -> Tree<N, usize> {
+    search(starts, neighbors, |_| false).0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::once;
+
+    use super::*;
+
+    /// A line of nodes from 0 to 9, each leading to the nodes either side of it
+    fn line(&n: &i32) -> impl Iterator<Item = i32> {
+        vec![n - 1, n + 1]
+            .into_iter()
+            .filter(|n| (0..10).contains(n))
+    }
+
+    #[test]
+    fn finds_the_shortest_path() {
+        let path = bfs(once(2), line, |&n| n == 5).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes, [2, 3, 4, 5]);
+
+        assert_eq!(bfs(once(2), line, |&n| n == 10), None);
+    }
+
+    #[test]
+    fn starts_from_every_start() {
+        let path = bfs(vec![0, 9], line, |&n| n == 7).unwrap();
+        assert_eq!(path.nodes, [9, 8, 7]);
+
+        let tree = bfs_all(vec![0, 9], line);
+        assert_eq!(tree.len(), 10);
+        assert_eq!(tree.iter().map(|(_, cost)| cost).max(), Some(4));
+        assert_eq!(tree.path(&4).unwrap().nodes, [0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{Cost, Graph, Node, Path, Tree};
+
+/// Search from the starts, always going on from the node whose cost so far plus `heuristic`'s
+/// guess of the cost left is cheapest, until every reachable node's been reached. Stops early at
+/// the first node for which `goal` returns true.
+fn search<N: Node, G: Graph<N>>(
+    starts: impl IntoIterator<Item = N>,
+    mut graph: G,
+    mut heuristic: impl FnMut(&N) -> G::Cost,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Tree<N, G::Cost>, Option<usize>) {
+    let mut tree = Tree::new();
+    let mut open = BinaryHeap::new();
+    let mut closed = Vec::new();
+
+    for start in starts {
+        if let Some(idx) = tree.reach(start, G::Cost::ZERO, None) {
+            open.push(Reverse((heuristic(tree.node(idx)), idx)));
+        }
+    }
+
+    while let Some(Reverse((_, idx))) = open.pop() {
+        // Reaching a node more cheaply leaves the old way there in the heap, to be skipped once the
+        // node's been gone on from
+        closed.resize(tree.len(), false);
+        if closed[idx] {
+            continue;
+        }
+        closed[idx] = true;
+
+        if goal(tree.node(idx)) {
+            return (tree, Some(idx));
+        }
+
+        let cost = tree.cost_at(idx);
+        for (neighbor, edge) in graph.edges(tree.node(idx)) {
+            if let Some(next) = tree.reach(neighbor, cost + edge, Some(idx)) {
+                // A heuristic which guesses too much for some nodes can get to a node which has
+                // already been gone on from more cheaply, and then it has to be gone on from again
+                if let Some(closed) = closed.get_mut(next) {
+                    *closed = false;
+                }
+
+                let guess = heuristic(tree.node(next));
+                open.push(Reverse((tree.cost_at(next) + guess, next)));
+            }
+        }
+    }
+
+    (tree, None)
+}
+
+/// Find the cheapest way from any of the starts to a node for which `goal` returns true. The
+/// graph's edges can't have negative costs.
+pub fn dijkstra<N: Node, G: Graph<N>>(
+    starts: impl IntoIterator<Item = N>,
+    graph: G,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, G::Cost>> {
+    let (tree, found) = search(starts, graph, |_| G::Cost::ZERO, goal);
+    found.map(|idx| tree.path_to(idx))
+}
+
+/// Find the cheapest way from any of the starts to every node reachable from them. The graph's
+/// edges can't have negative costs.
+pub fn dijkstra_all<N: Node, G: Graph<N>>(
+    starts: impl IntoIterator<Item = N>,
+    graph: G,
+) -> Tree<N, G::Cost> {
+    search(starts, graph, |_| G::Cost::ZERO, |_| false).0
+}
+
+/// Find the cheapest way from any of the starts to a node for which `goal` returns true, guided by
+/// `heuristic`. The way found is only sure to be the cheapest if the heuristic never guesses more
+/// than the cheapest way from a node to a goal costs.
+pub fn astar<N: Node, G: Graph<N>>(
+    starts: impl IntoIterator<Item = N>,
+    graph: G,
+    heuristic: impl FnMut(&N) -> G::Cost,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, G::Cost>> {
+    let (tree, found) = search(starts, graph, heuristic, goal);
+    found.map(|idx| tree.path_to(idx))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::once;
+
+    use super::*;
+
+    /// A few cities and the roads between them, where going straight from `a` to `d` is the long
+    /// way round
+    fn roads(&city: &char) -> Vec<(char, u32)> {
+        match city {
+            'a' => vec![('b', 2), ('c', 5), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_the_cheapest_path() {
+        let path = dijkstra(once('a'), roads, |&city| city == 'd').unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.nodes, ['a', 'b', 'c', 'd']);
+
+        assert_eq!(dijkstra(once('b'), roads, |&city| city == 'a'), None);
+
+        let tree = dijkstra_all(vec!['a', 'c'], roads);
+        assert_eq!(tree.cost(&'c'), Some(0));
+        assert_eq!(tree.cost(&'d'), Some(1));
+        assert_eq!(tree.path(&'b').unwrap().nodes, ['a', 'b']);
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        // How far each city is from `d` as the crow flies
+        let heuristic = |&city: &char| match city {
+            'a' => 4,
+            'b' => 3,
+            'c' => 1,
+            _ => 0,
+        };
+
+        let path = astar(once('a'), roads, heuristic, |&city| city == 'd').unwrap();
+        assert_eq!(
+            path,
+            dijkstra(once('a'), roads, |&city| city == 'd').unwrap()
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Searching graphs for the cheapest way from one node to another. A search can start from many
+//! nodes at once, and its nodes can be anything which can be hashed, so a node can be a position
+//! together with whatever else the puzzle needs to keep track of along the way.
+
+use std::{hash::Hash, ops::Add};
+
+mod bfs;
+mod dijkstra;
+mod tree;
+
+pub use bfs::{bfs, bfs_all};
+pub use dijkstra::{astar, dijkstra, dijkstra_all};
+pub use tree::Tree;
+
+/// Something a search can add up along a path and compare
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! cost {
+    ($($t:ty)*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+            }
+        )*
+    };
+}
+
+cost!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+/// Where a search can go from a node, and how much each edge there costs. Any function from a
+/// node to its edges is one.
+pub trait Graph<N> {
+    type Cost: Cost;
+    type Edges: IntoIterator<Item = (N, Self::Cost)>;
+
+    fn edges(&mut self, node: &N) -> Self::Edges;
+}
+
+impl<N, C, E, F> Graph<N> for F
+where
+    C: Cost,
+    E: IntoIterator<Item = (N, C)>,
+    F: FnMut(&N) -> E,
+{
+    type Cost = C;
+    type Edges = E;
+
+    fn edges(&mut self, node: &N) -> E {
+        self(node)
+    }
+}
+
+/// A way from one of a search's starts to a node, and what it costs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+
+    /// Every node along the way, from the start to the end
+    pub nodes: Vec<N>,
+}
+
+/// What a search's nodes need to be to keep track of which it's seen
+pub trait Node: Clone + Eq + Hash {}
+
+impl<N: Clone + Eq + Hash> Node for N {}
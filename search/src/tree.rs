@@ -0,0 +1,105 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::{Cost, Node, Path};
+
+#[derive(Clone, Debug)]
+struct Reached<N, C> {
+    node: N,
+    cost: C,
+    parent: Option<usize>,
+}
+
+/// Every node a search reached, with the cheapest way it found there
+#[derive(Clone, Debug)]
+pub struct Tree<N, C> {
+    reached: Vec<Reached<N, C>>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N: Node, C: Cost> Tree<N, C> {
+    pub(crate) fn new() -> Self {
+        Self {
+            reached: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Reach a node from the one at `parent`, or start from it if there's none, unless it's already
+    /// been reached as cheaply. Returns the node's index if it was.
+    pub(crate) fn reach(&mut self, node: N, cost: C, parent: Option<usize>) -> Option<usize> {
+        match self.indices.entry(node) {
+            Entry::Occupied(entry) => {
+                let idx = *entry.get();
+                let reached = &mut self.reached[idx];
+                if cost < reached.cost {
+                    reached.cost = cost;
+                    reached.parent = parent;
+                    Some(idx)
+                } else {
+                    None
+                }
+            }
+
+            Entry::Vacant(entry) => {
+                let idx = self.reached.len();
+                self.reached.push(Reached {
+                    node: entry.key().clone(),
+                    cost,
+                    parent,
+                });
+                entry.insert(idx);
+                Some(idx)
+            }
+        }
+    }
+
+    pub(crate) fn node(&self, idx: usize) -> &N {
+        &self.reached[idx].node
+    }
+
+    pub(crate) fn cost_at(&self, idx: usize) -> C {
+        self.reached[idx].cost
+    }
+
+    pub(crate) fn path_to(&self, idx: usize) -> Path<N, C> {
+        let cost = self.reached[idx].cost;
+        let mut nodes = Vec::new();
+        let mut next = Some(idx);
+        while let Some(idx) = next {
+            nodes.push(self.reached[idx].node.clone());
+            next = self.reached[idx].parent;
+        }
+        nodes.reverse();
+
+        Path { cost, nodes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    /// What the cheapest way to a node costs, if it was reached
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.indices.get(node).map(|&idx| self.cost_at(idx))
+    }
+
+    /// The cheapest way to a node, if it was reached
+    pub fn path(&self, node: &N) -> Option<Path<N, C>> {
+        self.indices.get(node).map(|&idx| self.path_to(idx))
+    }
+
+    /// Every node reached with what it costs to get there, in the order they were first reached
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> + '_ {
+        self.reached
+            .iter()
+            .map(|reached| (&reached.node, reached.cost))
+    }
+}